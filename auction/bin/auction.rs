@@ -0,0 +1,250 @@
+#![no_main]
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String};
+use auction::{Auction, AuctionHouse, AuctionKind};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, CLType, CLTyped, CLValue, ContractPackageHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U512,
+};
+use cep47::TokenId;
+use contract_utils::{ContractContext, OnChainContractStorage};
+
+#[derive(Default)]
+struct AuctionContract(OnChainContractStorage);
+
+impl ContractContext<OnChainContractStorage> for AuctionContract {
+    fn storage(&self) -> &OnChainContractStorage {
+        &self.0
+    }
+}
+
+impl AuctionHouse<OnChainContractStorage> for AuctionContract {}
+
+#[no_mangle]
+fn constructor() {
+    AuctionContract::default().init();
+}
+
+#[no_mangle]
+fn auction() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let ret = AuctionContract::default().auction(nft_contract, token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn current_price() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let ret = AuctionContract::default()
+        .current_price(nft_contract, token_id)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn create_english_auction() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let auction = Auction {
+        seller: runtime::get_caller().into(),
+        kind: AuctionKind::English,
+        start_time: runtime::get_named_arg::<u64>("start_time"),
+        end_time: runtime::get_named_arg::<u64>("end_time"),
+        reserve_price: runtime::get_named_arg::<U512>("reserve_price"),
+        start_price: U512::zero(),
+        extension: runtime::get_named_arg::<u64>("extension"),
+        highest_bid: None,
+    };
+    AuctionContract::default()
+        .create(nft_contract, token_id, auction)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn create_dutch_auction() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let auction = Auction {
+        seller: runtime::get_caller().into(),
+        kind: AuctionKind::Dutch,
+        start_time: runtime::get_named_arg::<u64>("start_time"),
+        end_time: runtime::get_named_arg::<u64>("end_time"),
+        reserve_price: runtime::get_named_arg::<U512>("reserve_price"),
+        start_price: runtime::get_named_arg::<U512>("start_price"),
+        extension: 0,
+        highest_bid: None,
+    };
+    AuctionContract::default()
+        .create(nft_contract, token_id, auction)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn bid() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let purse = runtime::get_named_arg::<URef>("purse");
+    AuctionContract::default()
+        .bid(nft_contract, token_id, purse)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn cancel() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    AuctionContract::default()
+        .cancel(nft_contract, token_id)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn settle() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    AuctionContract::default()
+        .settle(nft_contract, token_id)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn call() {
+    let contract_name: String = runtime::get_named_arg("contract_name");
+
+    let (contract_hash, _) = storage::new_contract(
+        get_entry_points(),
+        None,
+        Some(format!("{}_contract_package_hash", contract_name)),
+        None,
+    );
+
+    let package_hash: ContractPackageHash = ContractPackageHash::new(
+        runtime::get_key(&format!("{}_contract_package_hash", contract_name))
+            .unwrap_or_revert()
+            .into_hash()
+            .unwrap_or_revert(),
+    );
+
+    let constructor_access: URef =
+        storage::create_contract_user_group(package_hash, "constructor", 1, Default::default())
+            .unwrap_or_revert()
+            .pop()
+            .unwrap_or_revert();
+
+    let _: () = runtime::call_contract(contract_hash, "constructor", runtime_args! {});
+
+    let mut urefs = BTreeSet::new();
+    urefs.insert(constructor_access);
+    storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
+        .unwrap_or_revert();
+
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash_wrapped", contract_name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "constructor",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("constructor")]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "auction",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+        ],
+        CLType::Option(Box::new(Auction::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "current_price",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+        ],
+        U512::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_english_auction",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+            Parameter::new("start_time", u64::cl_type()),
+            Parameter::new("end_time", u64::cl_type()),
+            Parameter::new("reserve_price", U512::cl_type()),
+            Parameter::new("extension", u64::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_dutch_auction",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+            Parameter::new("start_time", u64::cl_type()),
+            Parameter::new("end_time", u64::cl_type()),
+            Parameter::new("start_price", U512::cl_type()),
+            Parameter::new("reserve_price", U512::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "bid",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+            Parameter::new("purse", URef::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "settle",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
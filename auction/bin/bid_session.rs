@@ -0,0 +1,32 @@
+#![no_main]
+#![no_std]
+
+use casper_contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractHash, Key, RuntimeArgs, U512};
+use cep47::TokenId;
+
+// Funds a fresh purse with `amount` and bids it on the auction.
+#[no_mangle]
+fn call() {
+    let auction_contract_hash: Key = runtime::get_named_arg("auction_contract_hash");
+    let nft_contract: Key = runtime::get_named_arg("nft_contract");
+    let token_id: TokenId = runtime::get_named_arg("token_id");
+    let amount: U512 = runtime::get_named_arg("amount");
+
+    let purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), purse, amount, None)
+        .unwrap_or_revert();
+
+    let _: () = runtime::call_contract(
+        ContractHash::new(auction_contract_hash.into_hash().unwrap_or_revert()),
+        "bid",
+        runtime_args! {
+            "nft_contract" => nft_contract,
+            "token_id" => token_id,
+            "purse" => purse
+        },
+    );
+}
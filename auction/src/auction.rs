@@ -0,0 +1,373 @@
+use crate::{
+    data::{self, Auctions},
+    event::AuctionEvent,
+};
+use alloc::{vec, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    runtime_args, ApiError, CLType, CLTyped, ContractHash, Key, RuntimeArgs, URef, U512,
+};
+use cep47::TokenId;
+use contract_utils::{ContractContext, ContractStorage};
+
+#[repr(u16)]
+pub enum Error {
+    PermissionDenied = 1,
+    WrongArguments = 2,
+    AuctionNotFound = 3,
+    AuctionAlreadyExists = 4,
+    AuctionNotActive = 5,
+    AuctionNotEnded = 6,
+    BidTooLow = 7,
+    InvalidPrice = 8,
+    AuctionHasBids = 9,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum AuctionKind {
+    /// Ascending bids, the highest one wins once the auction has ended.
+    English = 0,
+    /// Descending price, the first bid at or above the current price wins.
+    Dutch = 1,
+}
+
+pub struct Auction {
+    pub seller: Key,
+    pub kind: AuctionKind,
+    pub start_time: u64,
+    pub end_time: u64,
+    /// English: lowest acceptable bid. Dutch: price reached at `end_time`.
+    pub reserve_price: U512,
+    /// Dutch: price at `start_time`. Unused by English auctions.
+    pub start_price: U512,
+    /// English: a bid placed less than `extension` ms before the end moves
+    /// the end to `extension` ms after the bid. `0` disables extensions.
+    pub extension: u64,
+    pub highest_bid: Option<(Key, U512)>,
+}
+
+impl Auction {
+    /// Price a Dutch auction asks at `now`, falling linearly from
+    /// `start_price` to `reserve_price` over the auction window.
+    pub fn current_price(&self, now: u64) -> U512 {
+        if now <= self.start_time {
+            return self.start_price;
+        }
+        if now >= self.end_time {
+            return self.reserve_price;
+        }
+        let drop = self.start_price - self.reserve_price;
+        let elapsed = U512::from(now - self.start_time);
+        let duration = U512::from(self.end_time - self.start_time);
+        self.start_price - drop * elapsed / duration
+    }
+}
+
+impl ToBytes for Auction {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.seller.to_bytes()?);
+        result.append(&mut (self.kind as u8).to_bytes()?);
+        result.append(&mut self.start_time.to_bytes()?);
+        result.append(&mut self.end_time.to_bytes()?);
+        result.append(&mut self.reserve_price.to_bytes()?);
+        result.append(&mut self.start_price.to_bytes()?);
+        result.append(&mut self.extension.to_bytes()?);
+        result.append(&mut self.highest_bid.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.seller.serialized_length()
+            + (self.kind as u8).serialized_length()
+            + self.start_time.serialized_length()
+            + self.end_time.serialized_length()
+            + self.reserve_price.serialized_length()
+            + self.start_price.serialized_length()
+            + self.extension.serialized_length()
+            + self.highest_bid.serialized_length()
+    }
+}
+
+impl FromBytes for Auction {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (seller, bytes) = Key::from_bytes(bytes)?;
+        let (kind, bytes) = u8::from_bytes(bytes)?;
+        let kind = match kind {
+            0 => AuctionKind::English,
+            1 => AuctionKind::Dutch,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        let (start_time, bytes) = u64::from_bytes(bytes)?;
+        let (end_time, bytes) = u64::from_bytes(bytes)?;
+        let (reserve_price, bytes) = U512::from_bytes(bytes)?;
+        let (start_price, bytes) = U512::from_bytes(bytes)?;
+        let (extension, bytes) = u64::from_bytes(bytes)?;
+        let (highest_bid, bytes) = Option::<(Key, U512)>::from_bytes(bytes)?;
+        let auction = Auction {
+            seller,
+            kind,
+            start_time,
+            end_time,
+            reserve_price,
+            start_price,
+            extension,
+            highest_bid,
+        };
+        Ok((auction, bytes))
+    }
+}
+
+impl CLTyped for Auction {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+/// Timed auctions of CEP47 tokens. The token is escrowed with
+/// `transfer_from` when the auction is created, so the auction house has to
+/// be the approved spender, and handed out with `transfer` on settlement.
+/// Auctions pass numeric `TokenId`s to the collection, so string id
+/// collections aren't supported.
+pub trait AuctionHouse<Storage: ContractStorage>: ContractContext<Storage> {
+    fn init(&mut self) {
+        Auctions::init();
+        data::set_purse(system::create_purse());
+    }
+
+    fn auction(&self, nft_contract: Key, token_id: TokenId) -> Option<Auction> {
+        Auctions::instance().get(&nft_contract, &token_id)
+    }
+
+    fn current_price(&self, nft_contract: Key, token_id: TokenId) -> Result<U512, Error> {
+        let auction = self
+            .auction(nft_contract, token_id)
+            .ok_or(Error::AuctionNotFound)?;
+        Ok(match auction.kind {
+            AuctionKind::English => auction
+                .highest_bid
+                .map(|(_, amount)| amount)
+                .unwrap_or(auction.reserve_price),
+            AuctionKind::Dutch => auction.current_price(now()),
+        })
+    }
+
+    /// Escrows the token and opens the auction. `seller`, `highest_bid` are
+    /// filled in from the call.
+    fn create(
+        &mut self,
+        nft_contract: Key,
+        token_id: TokenId,
+        mut auction: Auction,
+    ) -> Result<(), Error> {
+        if auction.start_time >= auction.end_time {
+            return Err(Error::WrongArguments);
+        }
+        if auction.kind == AuctionKind::Dutch && auction.start_price <= auction.reserve_price {
+            return Err(Error::InvalidPrice);
+        }
+        // Proceeds are paid out to the seller's main purse.
+        let seller = self.get_caller();
+        if seller.into_account().is_none() {
+            return Err(Error::PermissionDenied);
+        }
+        let auctions_dict = Auctions::instance();
+        if auctions_dict.get(&nft_contract, &token_id).is_some() {
+            return Err(Error::AuctionAlreadyExists);
+        }
+
+        let _: () = runtime::call_contract(
+            to_contract_hash(nft_contract)?,
+            "transfer_from",
+            runtime_args! {
+                "sender" => seller,
+                "recipient" => self.self_addr(),
+                "token_ids" => vec![token_id]
+            },
+        );
+
+        auction.seller = seller;
+        auction.highest_bid = None;
+        auctions_dict.set(&nft_contract, &token_id, auction);
+        self.emit(AuctionEvent::Create {
+            nft_contract,
+            token_id,
+            seller,
+        });
+        Ok(())
+    }
+
+    /// Bids the whole balance of `purse`. English bids are held in the
+    /// contract purse and the previous highest bidder is refunded; a Dutch
+    /// bid at or above the current price settles the auction right away.
+    fn bid(&mut self, nft_contract: Key, token_id: TokenId, purse: URef) -> Result<(), Error> {
+        let auctions_dict = Auctions::instance();
+        let mut auction = auctions_dict
+            .get(&nft_contract, &token_id)
+            .ok_or(Error::AuctionNotFound)?;
+        let now = now();
+        if now < auction.start_time || now >= auction.end_time {
+            return Err(Error::AuctionNotActive);
+        }
+        let bidder = self.get_caller();
+        let bidder_account = bidder.into_account().ok_or(Error::PermissionDenied)?;
+        if bidder == auction.seller {
+            return Err(Error::PermissionDenied);
+        }
+        let amount = system::get_purse_balance(purse).unwrap_or_revert();
+
+        if auction.kind == AuctionKind::Dutch {
+            let price = auction.current_price(now);
+            if amount < price {
+                return Err(Error::BidTooLow);
+            }
+            auctions_dict.remove(&nft_contract, &token_id);
+            pay(purse, auction.seller, price);
+            if amount > price {
+                system::transfer_from_purse_to_account(purse, bidder_account, amount - price, None)
+                    .unwrap_or_revert();
+            }
+            self.emit(AuctionEvent::Bid {
+                nft_contract,
+                token_id,
+                bidder,
+                amount: price,
+            });
+            return self.finish(nft_contract, token_id, auction.seller, Some(bidder), price);
+        }
+
+        if amount < auction.reserve_price {
+            return Err(Error::BidTooLow);
+        }
+        if let Some((_, highest)) = auction.highest_bid {
+            if amount <= highest {
+                return Err(Error::BidTooLow);
+            }
+        }
+        system::transfer_from_purse_to_purse(purse, data::purse(), amount, None).unwrap_or_revert();
+        if let Some((previous_bidder, previous_amount)) = auction.highest_bid {
+            pay(data::purse(), previous_bidder, previous_amount);
+        }
+        auction.highest_bid = Some((bidder, amount));
+        if auction.end_time - now < auction.extension {
+            auction.end_time = now + auction.extension;
+        }
+        auctions_dict.set(&nft_contract, &token_id, auction);
+        self.emit(AuctionEvent::Bid {
+            nft_contract,
+            token_id,
+            bidder,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Withdraws an auction nobody has bid on and returns the token.
+    fn cancel(&mut self, nft_contract: Key, token_id: TokenId) -> Result<(), Error> {
+        let auctions_dict = Auctions::instance();
+        let auction = auctions_dict
+            .get(&nft_contract, &token_id)
+            .ok_or(Error::AuctionNotFound)?;
+        if auction.seller != self.get_caller() {
+            return Err(Error::PermissionDenied);
+        }
+        if auction.highest_bid.is_some() {
+            return Err(Error::AuctionHasBids);
+        }
+        auctions_dict.remove(&nft_contract, &token_id);
+        send_token(nft_contract, token_id, auction.seller)?;
+        self.emit(AuctionEvent::Cancel {
+            nft_contract,
+            token_id,
+            seller: auction.seller,
+        });
+        Ok(())
+    }
+
+    /// Closes an ended auction. The highest bid goes to the seller and the
+    /// token to the bidder, or back to the seller when there was no bid.
+    fn settle(&mut self, nft_contract: Key, token_id: TokenId) -> Result<(), Error> {
+        let auctions_dict = Auctions::instance();
+        let auction = auctions_dict
+            .get(&nft_contract, &token_id)
+            .ok_or(Error::AuctionNotFound)?;
+        if now() < auction.end_time {
+            return Err(Error::AuctionNotEnded);
+        }
+        auctions_dict.remove(&nft_contract, &token_id);
+        match auction.highest_bid {
+            Some((bidder, amount)) => {
+                pay(data::purse(), auction.seller, amount);
+                self.finish(nft_contract, token_id, auction.seller, Some(bidder), amount)
+            }
+            None => self.finish(nft_contract, token_id, auction.seller, None, U512::zero()),
+        }
+    }
+
+    fn finish(
+        &mut self,
+        nft_contract: Key,
+        token_id: TokenId,
+        seller: Key,
+        winner: Option<Key>,
+        price: U512,
+    ) -> Result<(), Error> {
+        send_token(nft_contract, token_id, winner.unwrap_or(seller))?;
+        self.emit(AuctionEvent::Settle {
+            nft_contract,
+            token_id,
+            seller,
+            winner,
+            price,
+        });
+        Ok(())
+    }
+
+    fn emit(&mut self, event: AuctionEvent) {
+        data::emit(&event);
+    }
+}
+
+fn now() -> u64 {
+    runtime::get_blocktime().into()
+}
+
+fn to_contract_hash(nft_contract: Key) -> Result<ContractHash, Error> {
+    nft_contract
+        .into_hash()
+        .map(ContractHash::new)
+        .ok_or(Error::WrongArguments)
+}
+
+fn pay(purse: URef, recipient: Key, amount: U512) {
+    system::transfer_from_purse_to_account(
+        purse,
+        recipient.into_account().unwrap_or_revert(),
+        amount,
+        None,
+    )
+    .unwrap_or_revert();
+}
+
+fn send_token(nft_contract: Key, token_id: TokenId, recipient: Key) -> Result<(), Error> {
+    let _: () = runtime::call_contract(
+        to_contract_hash(nft_contract)?,
+        "transfer",
+        runtime_args! {
+            "recipient" => recipient,
+            "token_ids" => vec![token_id]
+        },
+    );
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use casper_contract::{
+    contract_api::{
+        runtime::{self, get_call_stack},
+        storage,
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{system::CallStackElement, ContractPackageHash, Key, URef};
+use cep47::TokenId;
+use contract_utils::{key_and_value_to_str, Dict};
+
+use crate::{event::AuctionEvent, Auction};
+
+const AUCTIONS_DICT: &str = "auctions";
+const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+
+pub const PURSE: &str = "purse";
+
+/// Running auctions keyed by NFT contract and token id.
+pub struct Auctions {
+    dict: Dict,
+}
+
+impl Auctions {
+    pub fn instance() -> Auctions {
+        Auctions {
+            dict: Dict::instance(AUCTIONS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(AUCTIONS_DICT)
+    }
+
+    pub fn get(&self, nft_contract: &Key, token_id: &TokenId) -> Option<Auction> {
+        self.dict.get(&key_and_value_to_str(nft_contract, token_id))
+    }
+
+    pub fn set(&self, nft_contract: &Key, token_id: &TokenId, value: Auction) {
+        self.dict
+            .set(&key_and_value_to_str(nft_contract, token_id), value);
+    }
+
+    pub fn remove(&self, nft_contract: &Key, token_id: &TokenId) {
+        self.dict
+            .remove::<Auction>(&key_and_value_to_str(nft_contract, token_id));
+    }
+}
+
+/// Purse holding the current highest bid of every English auction.
+pub fn purse() -> URef {
+    *runtime::get_key(PURSE)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert()
+}
+
+pub fn set_purse(purse: URef) {
+    runtime::put_key(PURSE, purse.into());
+}
+
+pub fn contract_package_hash() -> ContractPackageHash {
+    let call_stacks = get_call_stack();
+    let last_entry = call_stacks.last().unwrap_or_revert();
+    let package_hash: Option<ContractPackageHash> = match last_entry {
+        CallStackElement::StoredContract {
+            contract_package_hash,
+            contract_hash: _,
+        } => Some(*contract_package_hash),
+        _ => None,
+    };
+    package_hash.unwrap_or_revert()
+}
+
+pub fn emit(event: &AuctionEvent) {
+    let mut events = Vec::new();
+    let package = contract_package_hash();
+    match event {
+        AuctionEvent::Create {
+            nft_contract,
+            token_id,
+            seller,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "auction_create".to_string());
+            param.insert("nft_contract", nft_contract.to_string());
+            param.insert("token_id", token_id.to_string());
+            param.insert("seller", seller.to_string());
+            events.push(param);
+        }
+        AuctionEvent::Bid {
+            nft_contract,
+            token_id,
+            bidder,
+            amount,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "auction_bid".to_string());
+            param.insert("nft_contract", nft_contract.to_string());
+            param.insert("token_id", token_id.to_string());
+            param.insert("bidder", bidder.to_string());
+            param.insert("amount", amount.to_string());
+            events.push(param);
+        }
+        AuctionEvent::Cancel {
+            nft_contract,
+            token_id,
+            seller,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "auction_cancel".to_string());
+            param.insert("nft_contract", nft_contract.to_string());
+            param.insert("token_id", token_id.to_string());
+            param.insert("seller", seller.to_string());
+            events.push(param);
+        }
+        AuctionEvent::Settle {
+            nft_contract,
+            token_id,
+            seller,
+            winner,
+            price,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "auction_settle".to_string());
+            param.insert("nft_contract", nft_contract.to_string());
+            param.insert("token_id", token_id.to_string());
+            param.insert("seller", seller.to_string());
+            if let Some(winner) = winner {
+                param.insert("winner", winner.to_string());
+            }
+            param.insert("price", price.to_string());
+            events.push(param);
+        }
+    };
+    for param in events {
+        let _: URef = storage::new_uref(param);
+    }
+}
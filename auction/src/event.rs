@@ -0,0 +1,28 @@
+use casper_types::{Key, U512};
+use cep47::TokenId;
+
+pub enum AuctionEvent {
+    Create {
+        nft_contract: Key,
+        token_id: TokenId,
+        seller: Key,
+    },
+    Bid {
+        nft_contract: Key,
+        token_id: TokenId,
+        bidder: Key,
+        amount: U512,
+    },
+    Cancel {
+        nft_contract: Key,
+        token_id: TokenId,
+        seller: Key,
+    },
+    Settle {
+        nft_contract: Key,
+        token_id: TokenId,
+        seller: Key,
+        winner: Option<Key>,
+        price: U512,
+    },
+}
@@ -0,0 +1,9 @@
+#![no_std]
+extern crate alloc;
+
+mod auction;
+pub mod data;
+pub mod event;
+
+pub use auction::{Auction, AuctionHouse, AuctionKind, Error};
+pub use contract_utils;
@@ -0,0 +1,157 @@
+#![no_main]
+#![no_std]
+
+//! Installs a CEP47 token in storage layout 1, the first one that could be
+//! upgraded: decimal token id item keys, plain account item keys and no
+//! admins. Only `mint` is exported, to fill the layout before the tests
+//! upgrade it with `cep47-token.wasm`.
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, ApiError, CLTyped, ContractPackageHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U256,
+};
+use contract_utils::{get_key, key_and_value_to_str, set_key, Dict};
+
+type TokenId = U256;
+type Meta = BTreeMap<String, String>;
+
+const STORAGE_VERSION: u32 = 1;
+
+fn key_to_str(key: &Key) -> String {
+    match key {
+        Key::Account(account) => account.to_string(),
+        _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+    }
+}
+
+#[no_mangle]
+fn constructor() {
+    set_key("name", runtime::get_named_arg::<String>("name"));
+    set_key("symbol", runtime::get_named_arg::<String>("symbol"));
+    set_key("meta", runtime::get_named_arg::<Meta>("meta"));
+    set_key("total_supply", U256::zero());
+    set_key("storage_version", STORAGE_VERSION);
+    for name in &[
+        "owners",
+        "metadata",
+        "owned_tokens_by_index",
+        "owned_indexes_by_token",
+        "balances",
+        "allowances",
+    ] {
+        Dict::init(name);
+    }
+}
+
+#[no_mangle]
+fn mint() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let token_metas = runtime::get_named_arg::<Vec<Meta>>("token_metas");
+
+    let owners = Dict::instance("owners");
+    let metadata = Dict::instance("metadata");
+    let tokens_by_index = Dict::instance("owned_tokens_by_index");
+    let indexes_by_token = Dict::instance("owned_indexes_by_token");
+    let balances = Dict::instance("balances");
+    let mut balance: U256 = balances.get(&key_to_str(&recipient)).unwrap_or_default();
+    for (token_id, token_meta) in token_ids.iter().zip(token_metas) {
+        owners.set(&token_id.to_string(), recipient);
+        metadata.set(&token_id.to_string(), token_meta);
+        indexes_by_token.set(&key_and_value_to_str(&recipient, token_id), balance);
+        tokens_by_index.set(&key_and_value_to_str(&recipient, &balance), *token_id);
+        balance += U256::one();
+    }
+    balances.set(&key_to_str(&recipient), balance);
+
+    let total_supply: U256 = get_key("total_supply").unwrap_or_revert();
+    set_key("total_supply", total_supply + U256::from(token_ids.len()));
+}
+
+#[no_mangle]
+fn call() {
+    let contract_name: String = runtime::get_named_arg("contract_name");
+    let constructor_args = runtime_args! {
+        "name" => runtime::get_named_arg::<String>("name"),
+        "symbol" => runtime::get_named_arg::<String>("symbol"),
+        "meta" => runtime::get_named_arg::<Meta>("meta")
+    };
+
+    let (contract_hash, _) = storage::new_contract(
+        get_entry_points(),
+        None,
+        Some(format!("{}_contract_package_hash", contract_name)),
+        Some(format!("{}_package_access_token", contract_name)),
+    );
+
+    let package_hash: ContractPackageHash = ContractPackageHash::new(
+        runtime::get_key(&format!("{}_contract_package_hash", contract_name))
+            .unwrap_or_revert()
+            .into_hash()
+            .unwrap_or_revert(),
+    );
+
+    let constructor_access: URef =
+        storage::create_contract_user_group(package_hash, "constructor", 1, Default::default())
+            .unwrap_or_revert()
+            .pop()
+            .unwrap_or_revert();
+
+    storage::create_contract_user_group(package_hash, "migrate", 0, Default::default())
+        .unwrap_or_revert();
+
+    let _: () = runtime::call_contract(contract_hash, "constructor", constructor_args);
+
+    let mut urefs = BTreeSet::new();
+    urefs.insert(constructor_access);
+    storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
+        .unwrap_or_revert();
+
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash_wrapped", contract_name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "constructor",
+        vec![
+            Parameter::new("name", String::cl_type()),
+            Parameter::new("symbol", String::cl_type()),
+            Parameter::new("meta", Meta::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("constructor")]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", Vec::<TokenId>::cl_type()),
+            Parameter::new("token_metas", Vec::<Meta>::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
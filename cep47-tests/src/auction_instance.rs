@@ -0,0 +1,100 @@
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+use test_env::{TestContract, TestEnv};
+
+use crate::cep47_instance::TokenId;
+
+pub struct AuctionInstance(TestContract, TestEnv);
+
+impl AuctionInstance {
+    pub fn new(env: &TestEnv, contract_name: &str, sender: AccountHash) -> AuctionInstance {
+        AuctionInstance(
+            TestContract::new(env, "auction.wasm", contract_name, sender, runtime_args! {}),
+            env.clone(),
+        )
+    }
+
+    pub fn package_key(&self) -> Key {
+        Key::Hash(self.0.package_hash())
+    }
+
+    pub fn create_english_auction(
+        &self,
+        sender: AccountHash,
+        nft_contract: Key,
+        token_id: TokenId,
+        window: (u64, u64),
+        reserve_price: U512,
+        extension: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "create_english_auction",
+            runtime_args! {
+                "nft_contract" => nft_contract,
+                "token_id" => token_id,
+                "start_time" => window.0,
+                "end_time" => window.1,
+                "reserve_price" => reserve_price,
+                "extension" => extension
+            },
+        )
+    }
+
+    pub fn create_dutch_auction(
+        &self,
+        sender: AccountHash,
+        nft_contract: Key,
+        token_id: TokenId,
+        window: (u64, u64),
+        start_price: U512,
+        reserve_price: U512,
+    ) {
+        self.0.call_contract(
+            sender,
+            "create_dutch_auction",
+            runtime_args! {
+                "nft_contract" => nft_contract,
+                "token_id" => token_id,
+                "start_time" => window.0,
+                "end_time" => window.1,
+                "start_price" => start_price,
+                "reserve_price" => reserve_price
+            },
+        )
+    }
+
+    pub fn bid(&self, sender: AccountHash, nft_contract: Key, token_id: TokenId, amount: U512) {
+        self.1.run_session(
+            sender,
+            "auction-bid-session.wasm",
+            runtime_args! {
+                "auction_contract_hash" => Key::Hash(self.0.contract_hash()),
+                "nft_contract" => nft_contract,
+                "token_id" => token_id,
+                "amount" => amount
+            },
+        )
+    }
+
+    pub fn cancel(&self, sender: AccountHash, nft_contract: Key, token_id: TokenId) {
+        self.0.call_contract(
+            sender,
+            "cancel",
+            runtime_args! {
+                "nft_contract" => nft_contract,
+                "token_id" => token_id
+            },
+        )
+    }
+
+    pub fn settle(&self, sender: AccountHash, nft_contract: Key, token_id: TokenId) {
+        self.0.call_contract(
+            sender,
+            "settle",
+            runtime_args! {
+                "nft_contract" => nft_contract,
+                "token_id" => token_id
+            },
+        )
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+
+use casper_types::{account::AccountHash, Key, U512};
+use test_env::TestEnv;
+
+use crate::{
+    auction_instance::AuctionInstance,
+    cep47_instance::{CEP47Instance, Meta, TokenId},
+};
+
+const NAME: &str = "DragonsNFT";
+const SYMBOL: &str = "DGNFT";
+const AUCTION: &str = "DragonsAuction";
+const WINDOW: (u64, u64) = (1_000, 2_000);
+
+mod meta {
+    use super::{BTreeMap, Meta};
+    pub fn contract_meta() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("origin".to_string(), "fire".to_string());
+        meta
+    }
+
+    pub fn red_dragon() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("color".to_string(), "red".to_string());
+        meta
+    }
+}
+
+fn cspr(amount: u64) -> U512 {
+    U512::from(amount) * U512::from(1_000_000_000u64)
+}
+
+struct Setup {
+    env: TestEnv,
+    token: CEP47Instance,
+    auction: AuctionInstance,
+    owner: AccountHash,
+    seller: AccountHash,
+}
+
+fn deploy() -> Setup {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let seller = env.next_user();
+    let token = CEP47Instance::new(&env, NAME, owner, NAME, SYMBOL, meta::contract_meta());
+    let auction = AuctionInstance::new(&env, AUCTION, owner);
+    token.mint_one(owner, seller, TokenId::zero(), meta::red_dragon());
+    token.approve(seller, auction.package_key(), vec![TokenId::zero()]);
+    Setup {
+        env,
+        token,
+        auction,
+        owner,
+        seller,
+    }
+}
+
+fn english(setup: &Setup, reserve_price: U512, extension: u64) {
+    setup.auction.create_english_auction(
+        setup.seller,
+        setup.token.contract_key(),
+        TokenId::zero(),
+        WINDOW,
+        reserve_price,
+        extension,
+    );
+}
+
+#[test]
+fn test_english_auction() {
+    let setup = deploy();
+    let nft = setup.token.contract_key();
+    let alice = setup.env.next_user();
+    let bob = setup.env.next_user();
+    english(&setup, cspr(10), 0);
+    assert_eq!(
+        setup.token.owner_of(TokenId::zero()).unwrap(),
+        setup.auction.package_key()
+    );
+
+    setup.env.set_block_time(1_500);
+    setup.auction.bid(alice, nft, TokenId::zero(), cspr(10));
+    let alice_balance = setup.env.account_balance(alice);
+    setup.auction.bid(bob, nft, TokenId::zero(), cspr(20));
+    assert_eq!(setup.env.account_balance(alice), alice_balance + cspr(10));
+
+    let seller_balance = setup.env.account_balance(setup.seller);
+    setup.env.set_block_time(WINDOW.1);
+    setup.auction.settle(setup.owner, nft, TokenId::zero());
+    assert_eq!(
+        setup.token.owner_of(TokenId::zero()).unwrap(),
+        Key::Account(bob)
+    );
+    assert_eq!(
+        setup.env.account_balance(setup.seller),
+        seller_balance + cspr(20)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_bid_below_reserve() {
+    let setup = deploy();
+    let alice = setup.env.next_user();
+    english(&setup, cspr(10), 0);
+    setup.env.set_block_time(1_500);
+    setup
+        .auction
+        .bid(alice, setup.token.contract_key(), TokenId::zero(), cspr(9));
+}
+
+#[test]
+#[should_panic]
+fn test_bid_not_above_highest() {
+    let setup = deploy();
+    let nft = setup.token.contract_key();
+    let alice = setup.env.next_user();
+    let bob = setup.env.next_user();
+    english(&setup, cspr(10), 0);
+    setup.env.set_block_time(1_500);
+    setup.auction.bid(alice, nft, TokenId::zero(), cspr(15));
+    setup.auction.bid(bob, nft, TokenId::zero(), cspr(15));
+}
+
+#[test]
+#[should_panic]
+fn test_bid_after_end() {
+    let setup = deploy();
+    let alice = setup.env.next_user();
+    english(&setup, cspr(10), 0);
+    setup.env.set_block_time(WINDOW.1);
+    setup
+        .auction
+        .bid(alice, setup.token.contract_key(), TokenId::zero(), cspr(10));
+}
+
+#[test]
+fn test_late_bid_extends_auction() {
+    let setup = deploy();
+    let nft = setup.token.contract_key();
+    let alice = setup.env.next_user();
+    let bob = setup.env.next_user();
+    english(&setup, cspr(10), 300);
+
+    setup.env.set_block_time(1_900);
+    setup.auction.bid(alice, nft, TokenId::zero(), cspr(10));
+    setup.env.set_block_time(2_100);
+    setup.auction.bid(bob, nft, TokenId::zero(), cspr(11));
+
+    setup.env.set_block_time(2_400);
+    setup.auction.settle(setup.owner, nft, TokenId::zero());
+    assert_eq!(
+        setup.token.owner_of(TokenId::zero()).unwrap(),
+        Key::Account(bob)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_settle_before_end() {
+    let setup = deploy();
+    english(&setup, cspr(10), 0);
+    setup.env.set_block_time(1_500);
+    setup
+        .auction
+        .settle(setup.owner, setup.token.contract_key(), TokenId::zero());
+}
+
+#[test]
+fn test_settle_without_bids() {
+    let setup = deploy();
+    english(&setup, cspr(10), 0);
+    setup.env.set_block_time(WINDOW.1);
+    setup
+        .auction
+        .settle(setup.owner, setup.token.contract_key(), TokenId::zero());
+    assert_eq!(
+        setup.token.owner_of(TokenId::zero()).unwrap(),
+        Key::Account(setup.seller)
+    );
+}
+
+#[test]
+fn test_cancel() {
+    let setup = deploy();
+    english(&setup, cspr(10), 0);
+    setup
+        .auction
+        .cancel(setup.seller, setup.token.contract_key(), TokenId::zero());
+    assert_eq!(
+        setup.token.owner_of(TokenId::zero()).unwrap(),
+        Key::Account(setup.seller)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_cancel_with_bids() {
+    let setup = deploy();
+    let nft = setup.token.contract_key();
+    let alice = setup.env.next_user();
+    english(&setup, cspr(10), 0);
+    setup.env.set_block_time(1_500);
+    setup.auction.bid(alice, nft, TokenId::zero(), cspr(10));
+    setup.auction.cancel(setup.seller, nft, TokenId::zero());
+}
+
+#[test]
+fn test_dutch_auction() {
+    let setup = deploy();
+    let nft = setup.token.contract_key();
+    let alice = setup.env.next_user();
+    setup.auction.create_dutch_auction(
+        setup.seller,
+        nft,
+        TokenId::zero(),
+        WINDOW,
+        cspr(100),
+        cspr(20),
+    );
+
+    let seller_balance = setup.env.account_balance(setup.seller);
+    setup.env.set_block_time(1_500);
+    setup.auction.bid(alice, nft, TokenId::zero(), cspr(70));
+    assert_eq!(
+        setup.token.owner_of(TokenId::zero()).unwrap(),
+        Key::Account(alice)
+    );
+    assert_eq!(
+        setup.env.account_balance(setup.seller),
+        seller_balance + cspr(60)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_dutch_bid_below_price() {
+    let setup = deploy();
+    let nft = setup.token.contract_key();
+    let alice = setup.env.next_user();
+    setup.auction.create_dutch_auction(
+        setup.seller,
+        nft,
+        TokenId::zero(),
+        WINDOW,
+        cspr(100),
+        cspr(20),
+    );
+    setup.env.set_block_time(1_500);
+    setup.auction.bid(alice, nft, TokenId::zero(), cspr(50));
+}
@@ -5,14 +5,16 @@ use blake2::{
     VarBlake2b,
 };
 use casper_types::{
-    account::AccountHash, bytesrepr::ToBytes, runtime_args, CLTyped, Key, RuntimeArgs, U256,
+    account::AccountHash, bytesrepr::ToBytes, runtime_args, CLTyped, Key, RuntimeArgs, URef, U256,
+    U512,
 };
 use test_env::{TestContract, TestEnv};
 
 pub type TokenId = U256;
 pub type Meta = BTreeMap<String, String>;
+pub type Checksum = (String, String);
 
-pub struct CEP47Instance(TestContract);
+pub struct CEP47Instance(TestContract, TestEnv);
 
 impl CEP47Instance {
     pub fn new(
@@ -23,17 +25,54 @@ impl CEP47Instance {
         symbol: &str,
         meta: Meta,
     ) -> CEP47Instance {
-        CEP47Instance(TestContract::new(
+        CEP47Instance::new_with_args(
             env,
-            "cep47-token.wasm",
             contract_name,
             sender,
-            runtime_args! {
-                "name" => name,
-                "symbol" => symbol,
-                "meta" => meta
-            },
-        ))
+            name,
+            symbol,
+            meta,
+            runtime_args! {},
+        )
+    }
+
+    /// Installs with optional install-time settings passed in `args`.
+    pub fn new_with_args(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        name: &str,
+        symbol: &str,
+        meta: Meta,
+        mut args: RuntimeArgs,
+    ) -> CEP47Instance {
+        args.insert("name", name).unwrap();
+        args.insert("symbol", symbol).unwrap();
+        args.insert("meta", meta).unwrap();
+        CEP47Instance(
+            TestContract::new(env, "cep47-token.wasm", contract_name, sender, args),
+            env.clone(),
+        )
+    }
+
+    /// Installs a collection in storage layout 1, see `cep47-fixtures`.
+    pub fn new_v1(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        name: &str,
+        symbol: &str,
+        meta: Meta,
+    ) -> CEP47Instance {
+        let args = runtime_args! {
+            "name" => name,
+            "symbol" => symbol,
+            "meta" => meta
+        };
+        CEP47Instance(
+            TestContract::new(env, "cep47-v1-token.wasm", contract_name, sender, args),
+            env.clone(),
+        )
     }
 
     pub fn constructor(&self, sender: AccountHash, name: &str, symbol: &str, meta: Meta) {
@@ -47,6 +86,35 @@ impl CEP47Instance {
         );
     }
 
+    pub fn migrate_token_keys(&self, sender: AccountHash, token_ids: Vec<TokenId>) {
+        self.0.call_contract(
+            sender,
+            "migrate_token_keys",
+            runtime_args! { "token_ids" => token_ids },
+        )
+    }
+
+    pub fn migrate_account_keys(&self, sender: AccountHash, accounts: Vec<Key>) {
+        self.0.call_contract(
+            sender,
+            "migrate_account_keys",
+            runtime_args! { "accounts" => accounts },
+        )
+    }
+
+    /// Calls the `balance_of` entry point, whose result the deploy discards.
+    pub fn call_balance_of<T: Into<Key>>(&self, sender: AccountHash, owner: T) {
+        self.0.call_contract(
+            sender,
+            "balance_of",
+            runtime_args! { "owner" => owner.into() },
+        )
+    }
+
+    pub fn upgrade(&self) {
+        self.0.upgrade("cep47-token.wasm", runtime_args! {});
+    }
+
     pub fn mint_one<T: Into<Key>>(
         &self,
         sender: AccountHash,
@@ -65,6 +133,26 @@ impl CEP47Instance {
         )
     }
 
+    pub fn mint_with_checksums<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        token_ids: Vec<TokenId>,
+        token_metas: Vec<Meta>,
+        token_checksums: Vec<Checksum>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "mint_with_checksums",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "token_ids" => token_ids,
+                "token_metas" => token_metas,
+                "token_checksums" => token_checksums
+            },
+        )
+    }
+
     pub fn mint_copies<T: Into<Key>>(
         &self,
         sender: AccountHash,
@@ -103,6 +191,209 @@ impl CEP47Instance {
         )
     }
 
+    pub fn purchase_mint<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        token_ids: Vec<TokenId>,
+        amount: U512,
+    ) {
+        self.1.run_session(
+            sender,
+            "purchase-mint-session.wasm",
+            runtime_args! {
+                "nft_contract_hash" => Key::Hash(self.0.contract_hash()),
+                "amount" => amount,
+                "recipient" => recipient.into(),
+                "token_ids" => token_ids
+            },
+        )
+    }
+
+    pub fn list_sale_items(
+        &self,
+        sender: AccountHash,
+        token_ids: Vec<TokenId>,
+        token_metas: Vec<Meta>,
+        tier: Option<String>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "list_sale_items",
+            runtime_args! {
+                "token_ids" => token_ids,
+                "token_metas" => token_metas,
+                "tier" => tier
+            },
+        )
+    }
+
+    pub fn unlist_sale_items(&self, sender: AccountHash, token_ids: Vec<TokenId>) {
+        self.0.call_contract(
+            sender,
+            "unlist_sale_items",
+            runtime_args! {"token_ids" => token_ids},
+        )
+    }
+
+    pub fn sale_item(&self, token_id: TokenId) -> Option<(Option<String>, Meta)> {
+        self.0.query_dictionary("sale_items", token_key(token_id))
+    }
+
+    pub fn set_mint_price(&self, sender: AccountHash, price: Option<U512>) {
+        self.0
+            .call_contract(sender, "set_mint_price", runtime_args! {"price" => price})
+    }
+
+    pub fn set_tier_price(&self, sender: AccountHash, tier: &str, price: Option<U512>) {
+        self.0.call_contract(
+            sender,
+            "set_tier_price",
+            runtime_args! {"tier" => tier, "price" => price},
+        )
+    }
+
+    pub fn set_max_mint_per_wallet(&self, sender: AccountHash, limit: Option<u32>) {
+        self.0.call_contract(
+            sender,
+            "set_max_mint_per_wallet",
+            runtime_args! {"limit" => limit},
+        )
+    }
+
+    pub fn wallet_minted<T: Into<Key>>(&self, account: T) -> u32 {
+        self.0
+            .query_dictionary("wallet_mints", key_to_str(&account.into()))
+            .unwrap_or_default()
+    }
+
+    pub fn set_treasury(&self, sender: AccountHash, treasury: AccountHash) {
+        self.0.call_contract(
+            sender,
+            "set_treasury",
+            runtime_args! {"treasury" => treasury},
+        )
+    }
+
+    pub fn withdraw(&self, sender: AccountHash, amount: U512) {
+        self.0
+            .call_contract(sender, "withdraw", runtime_args! {"amount" => amount})
+    }
+
+    pub fn set_mint_phase(
+        &self,
+        sender: AccountHash,
+        phase: &str,
+        window: (u64, u64),
+        wallet_limit: u32,
+        price: U512,
+        merkle_root: Option<[u8; 32]>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "set_mint_phase",
+            runtime_args! {
+                "phase" => phase,
+                "start_time" => window.0,
+                "end_time" => window.1,
+                "wallet_limit" => wallet_limit,
+                "price" => price,
+                "merkle_root" => merkle_root
+            },
+        )
+    }
+
+    pub fn set_allowlist<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        phase: &str,
+        accounts: Vec<T>,
+        allowed: bool,
+    ) {
+        let accounts: Vec<Key> = accounts.into_iter().map(Into::into).collect();
+        self.0.call_contract(
+            sender,
+            "set_allowlist",
+            runtime_args! {
+                "phase" => phase,
+                "accounts" => accounts,
+                "allowed" => allowed
+            },
+        )
+    }
+
+    pub fn phase_mint(
+        &self,
+        sender: AccountHash,
+        phase: &str,
+        token_ids: Vec<TokenId>,
+        proof: Vec<[u8; 32]>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "phase_mint",
+            runtime_args! {
+                "phase" => phase,
+                "token_ids" => token_ids,
+                "proof" => proof,
+                "purse" => Option::<URef>::None
+            },
+        )
+    }
+
+    /// Calls `phase_mint` with caller supplied `token_metas` as well, which
+    /// the contract must not use.
+    pub fn phase_mint_with_metas(
+        &self,
+        sender: AccountHash,
+        phase: &str,
+        token_ids: Vec<TokenId>,
+        token_metas: Vec<Meta>,
+        proof: Vec<[u8; 32]>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "phase_mint",
+            runtime_args! {
+                "phase" => phase,
+                "token_ids" => token_ids,
+                "token_metas" => token_metas,
+                "proof" => proof,
+                "purse" => Option::<URef>::None
+            },
+        )
+    }
+
+    pub fn phase_mint_paid(
+        &self,
+        sender: AccountHash,
+        phase: &str,
+        token_ids: Vec<TokenId>,
+        proof: Vec<[u8; 32]>,
+        amount: U512,
+    ) {
+        self.1.run_session(
+            sender,
+            "phase-mint-session.wasm",
+            runtime_args! {
+                "nft_contract_hash" => Key::Hash(self.0.contract_hash()),
+                "amount" => amount,
+                "phase" => phase,
+                "token_ids" => token_ids,
+                "proof" => proof
+            },
+        )
+    }
+
+    pub fn phase_minted<T: Into<Key>>(&self, phase: &str, account: T) -> u32 {
+        self.0
+            .query_dictionary(
+                "phase_mints",
+                key_and_value_to_str::<String>(&account.into(), &phase.to_string()),
+            )
+            .unwrap_or_default()
+    }
+
     pub fn burn_one<T: Into<Key>>(&self, sender: AccountHash, owner: T, token_id: TokenId) {
         self.0.call_contract(
             sender,
@@ -125,6 +416,22 @@ impl CEP47Instance {
         )
     }
 
+    pub fn burn_as_admin<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        owner: T,
+        token_ids: Vec<TokenId>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "burn_as_admin",
+            runtime_args! {
+                "owner" => owner.into(),
+                "token_ids" => token_ids
+            },
+        )
+    }
+
     pub fn transfer<T: Into<Key>>(
         &self,
         sender: AccountHash,
@@ -179,6 +486,46 @@ impl CEP47Instance {
         )
     }
 
+    pub fn attach_child(&self, sender: AccountHash, parent_id: TokenId, token_ids: Vec<TokenId>) {
+        self.0.call_contract(
+            sender,
+            "attach_child",
+            runtime_args! {
+                "parent_id" => parent_id,
+                "token_ids" => token_ids
+            },
+        )
+    }
+
+    pub fn detach_child(&self, sender: AccountHash, parent_id: TokenId, token_ids: Vec<TokenId>) {
+        self.0.call_contract(
+            sender,
+            "detach_child",
+            runtime_args! {
+                "parent_id" => parent_id,
+                "token_ids" => token_ids
+            },
+        )
+    }
+
+    pub fn parent_of(&self, token_id: TokenId) -> Option<TokenId> {
+        self.0.query_dictionary("parents", token_key(token_id))
+    }
+
+    pub fn children_of(&self, token_id: TokenId) -> Vec<TokenId> {
+        self.0
+            .query_dictionary("children", token_key(token_id))
+            .unwrap_or_default()
+    }
+
+    pub fn root_owner_of(&self, token_id: TokenId) -> Option<Key> {
+        let mut root_id = token_id;
+        while let Some(parent_id) = self.parent_of(root_id) {
+            root_id = parent_id;
+        }
+        self.owner_of(root_id)
+    }
+
     pub fn update_token_meta(&self, sender: AccountHash, token_id: TokenId, token_meta: Meta) {
         self.0.call_contract(
             sender,
@@ -190,6 +537,167 @@ impl CEP47Instance {
         )
     }
 
+    pub fn update_token_meta_and_checksum(
+        &self,
+        sender: AccountHash,
+        token_id: TokenId,
+        token_meta: Meta,
+        token_checksum: Checksum,
+    ) {
+        self.0.call_contract(
+            sender,
+            "update_token_meta_and_checksum",
+            runtime_args! {
+                "token_id" => token_id,
+                "token_meta" => token_meta,
+                "token_checksum" => token_checksum
+            },
+        )
+    }
+
+    pub fn token_checksum(&self, token_id: TokenId) -> Option<Checksum> {
+        self.0.query_dictionary("checksums", token_key(token_id))
+    }
+
+    pub fn update_token_meta_keys(
+        &self,
+        sender: AccountHash,
+        token_id: TokenId,
+        set: Meta,
+        remove: Vec<String>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "update_token_meta_keys",
+            runtime_args! {
+                "token_id" => token_id,
+                "set" => set,
+                "remove" => remove
+            },
+        )
+    }
+
+    pub fn freeze_token_meta(&self, sender: AccountHash, token_ids: Vec<TokenId>) {
+        self.0.call_contract(
+            sender,
+            "freeze_token_meta",
+            runtime_args! {
+                "token_ids" => token_ids
+            },
+        )
+    }
+
+    pub fn freeze_all_meta(&self, sender: AccountHash) {
+        self.0
+            .call_contract(sender, "freeze_all_meta", runtime_args! {})
+    }
+
+    pub fn set_base_uri(&self, sender: AccountHash, base_uri: &str) {
+        self.0.call_contract(
+            sender,
+            "set_base_uri",
+            runtime_args! { "base_uri" => base_uri },
+        )
+    }
+
+    pub fn set_token_uri(&self, sender: AccountHash, token_id: TokenId, uri: Option<&str>) {
+        self.0.call_contract(
+            sender,
+            "set_token_uri",
+            runtime_args! {
+                "token_id" => token_id,
+                "uri" => uri.map(String::from)
+            },
+        )
+    }
+
+    pub fn base_uri(&self) -> String {
+        self.0.query_named_key(String::from("base_uri"))
+    }
+
+    pub fn token_uri_override(&self, token_id: TokenId) -> Option<String> {
+        self.0.query_dictionary("token_uris", token_key(token_id))
+    }
+
+    pub fn reveal(&self, sender: AccountHash, base_uri: &str, offset_seed: U256) {
+        self.0.call_contract(
+            sender,
+            "reveal",
+            runtime_args! {
+                "base_uri" => base_uri,
+                "offset_seed" => offset_seed
+            },
+        )
+    }
+
+    pub fn reveal_token_meta(
+        &self,
+        sender: AccountHash,
+        token_ids: Vec<TokenId>,
+        token_metas: Vec<Meta>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "reveal_token_meta",
+            runtime_args! {
+                "token_ids" => token_ids,
+                "token_metas" => token_metas
+            },
+        )
+    }
+
+    pub fn meta_commitment(&self, token_id: TokenId) -> Option<String> {
+        self.0
+            .query_dictionary("meta_commitments", token_key(token_id))
+    }
+
+    pub fn provenance_hash(&self) -> Option<String> {
+        self.0.query_named_key(String::from("provenance_hash"))
+    }
+
+    pub fn placeholder_meta(&self) -> Option<Meta> {
+        self.0.query_named_key(String::from("placeholder_meta"))
+    }
+
+    pub fn reveal_offset(&self) -> Option<U256> {
+        self.0.query_named_key(String::from("reveal_offset"))
+    }
+
+    pub fn set_name(&self, sender: AccountHash, name: &str) {
+        self.0
+            .call_contract(sender, "set_name", runtime_args! { "name" => name })
+    }
+
+    pub fn set_symbol(&self, sender: AccountHash, symbol: &str) {
+        self.0
+            .call_contract(sender, "set_symbol", runtime_args! { "symbol" => symbol })
+    }
+
+    pub fn set_meta(&self, sender: AccountHash, meta: Meta) {
+        self.0
+            .call_contract(sender, "set_meta", runtime_args! { "meta" => meta })
+    }
+
+    pub fn update_meta_keys(&self, sender: AccountHash, set: Meta, remove: Vec<String>) {
+        self.0.call_contract(
+            sender,
+            "update_meta_keys",
+            runtime_args! {
+                "set" => set,
+                "remove" => remove
+            },
+        )
+    }
+
+    pub fn is_meta_frozen(&self, token_id: TokenId) -> bool {
+        let all_frozen: bool = self.0.query_named_key(String::from("all_meta_frozen"));
+        all_frozen
+            || self
+                .0
+                .query_dictionary("frozen_metadata", token_key(token_id))
+                .unwrap_or_default()
+    }
+
     pub fn get_token_by_index<T: Into<Key>>(&self, account: T, index: U256) -> Option<TokenId> {
         self.0.query_dictionary(
             "owned_tokens_by_index",
@@ -204,11 +712,23 @@ impl CEP47Instance {
     }
 
     pub fn owner_of(&self, token_id: TokenId) -> Option<Key> {
-        self.0.query_dictionary("owners", token_id.to_string())
+        self.0.query_dictionary("owners", token_key(token_id))
     }
 
     pub fn token_meta(&self, token_id: TokenId) -> Option<Meta> {
-        self.0.query_dictionary("metadata", token_id.to_string())
+        self.0.query_dictionary("metadata", token_key(token_id))
+    }
+
+    pub fn token_meta_version(&self, token_id: TokenId) -> u32 {
+        self.0
+            .query_dictionary("metadata_versions", token_key(token_id))
+            .unwrap_or_default()
+    }
+
+    pub fn token_meta_history(&self, token_id: TokenId, version: u32) -> Option<Meta> {
+        let bytes = (token_key(token_id), version).to_bytes().unwrap();
+        self.0
+            .query_dictionary("metadata_history", hex::encode(blake2b256(&bytes)))
     }
 
     pub fn name(&self) -> String {
@@ -226,14 +746,65 @@ impl CEP47Instance {
     pub fn meta(&self) -> Meta {
         self.0.query_named_key(String::from("meta"))
     }
+
+    pub fn is_burned(&self, token_id: TokenId) -> bool {
+        self.0
+            .query_dictionary::<Key>("burned_tokens", token_key(token_id))
+            .is_some()
+    }
+
+    pub fn burned_supply(&self) -> U256 {
+        self.0.query_named_key(String::from("burned_supply"))
+    }
+
+    pub fn recipient_policy(&self) -> u8 {
+        self.0.query_named_key(String::from("recipient_policy"))
+    }
+
+    pub fn burn_policy(&self) -> u8 {
+        self.0.query_named_key(String::from("burn_policy"))
+    }
+
+    pub fn mint_price(&self) -> Option<U512> {
+        self.0.query_named_key(String::from("mint_price"))
+    }
+
+    pub fn storage_version(&self) -> u32 {
+        self.0.query_named_key(String::from("storage_version"))
+    }
+
+    pub fn contract_hash(&self) -> [u8; 32] {
+        self.0.contract_hash()
+    }
+
+    pub fn contract_key(&self) -> Key {
+        Key::Hash(self.0.contract_hash())
+    }
+
+    pub fn package_key(&self) -> Key {
+        Key::Hash(self.0.package_hash())
+    }
 }
 
+/// Item key the contracts store `key` under in their `Key` dictionaries.
 pub fn key_to_str(key: &Key) -> String {
-    match key {
-        Key::Account(account) => account.to_string(),
-        Key::Hash(package) => hex::encode(package),
-        _ => panic!("Unexpected key type"),
-    }
+    let key = match key {
+        Key::URef(uref) => Key::URef(uref.remove_access_rights()),
+        _ => *key,
+    };
+    hex::encode(blake2b256(&key.to_bytes().unwrap()))
+}
+
+/// Digest an unrevealed collection stores in place of `meta`.
+pub fn meta_commitment(meta: &Meta) -> String {
+    hex::encode(blake2b256(&meta.to_bytes().unwrap()))
+}
+
+/// Item key the contract stores `token_id` under in its token dictionaries.
+pub fn token_key(token_id: TokenId) -> String {
+    let mut bytes = [0u8; 32];
+    token_id.to_big_endian(&mut bytes);
+    hex::encode(bytes)
 }
 
 pub fn key_and_value_to_str<T: CLTyped + ToBytes>(key: &Key, value: &T) -> String {
@@ -244,3 +815,16 @@ pub fn key_and_value_to_str<T: CLTyped + ToBytes>(key: &Key, value: &T) -> Strin
     hasher.finalize_variable(|hash| ret.clone_from_slice(hash));
     hex::encode(ret)
 }
+
+pub fn blake2b256(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = VarBlake2b::new(32).unwrap();
+    hasher.update(bytes);
+    let mut ret = [0u8; 32];
+    hasher.finalize_variable(|hash| ret.clone_from_slice(hash));
+    ret
+}
+
+pub fn merkle_parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    blake2b256(&[first, second].concat())
+}
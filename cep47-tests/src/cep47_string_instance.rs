@@ -0,0 +1,323 @@
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U256, U512};
+use test_env::{TestContract, TestEnv};
+
+use crate::cep47_instance::{blake2b256, key_and_value_to_str, key_to_str, Meta};
+
+pub type TokenId = String;
+
+pub struct CEP47StringInstance(TestContract, TestEnv);
+
+impl CEP47StringInstance {
+    pub fn new(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        name: &str,
+        symbol: &str,
+        meta: Meta,
+    ) -> CEP47StringInstance {
+        CEP47StringInstance::new_with_args(
+            env,
+            contract_name,
+            sender,
+            name,
+            symbol,
+            meta,
+            runtime_args! {},
+        )
+    }
+
+    /// Installs with optional install-time settings passed in `args`.
+    pub fn new_with_args(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        name: &str,
+        symbol: &str,
+        meta: Meta,
+        mut args: RuntimeArgs,
+    ) -> CEP47StringInstance {
+        args.insert("name", name).unwrap();
+        args.insert("symbol", symbol).unwrap();
+        args.insert("meta", meta).unwrap();
+        CEP47StringInstance(
+            TestContract::new(env, "cep47-string-token.wasm", contract_name, sender, args),
+            env.clone(),
+        )
+    }
+
+    pub fn mint_one<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        token_id: &str,
+        token_meta: Meta,
+    ) {
+        self.mint_many(sender, recipient, vec![token_id], vec![token_meta])
+    }
+
+    pub fn mint_many<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        token_ids: Vec<&str>,
+        token_metas: Vec<Meta>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "mint",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "token_ids" => to_ids(token_ids),
+                "token_metas" => token_metas
+            },
+        )
+    }
+
+    pub fn mint_copies<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        token_ids: Vec<&str>,
+        token_meta: Meta,
+        count: u32,
+    ) {
+        self.0.call_contract(
+            sender,
+            "mint_copies",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "token_ids" => to_ids(token_ids),
+                "token_meta" => token_meta,
+                "count" => count
+            },
+        )
+    }
+
+    pub fn purchase_mint<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        token_ids: Vec<&str>,
+        amount: U512,
+    ) {
+        self.1.run_session(
+            sender,
+            "purchase-mint-string-session.wasm",
+            runtime_args! {
+                "nft_contract_hash" => Key::Hash(self.0.contract_hash()),
+                "amount" => amount,
+                "recipient" => recipient.into(),
+                "token_ids" => to_ids(token_ids)
+            },
+        )
+    }
+
+    pub fn list_sale_items(
+        &self,
+        sender: AccountHash,
+        token_ids: Vec<&str>,
+        token_metas: Vec<Meta>,
+        tier: Option<String>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "list_sale_items",
+            runtime_args! {
+                "token_ids" => to_ids(token_ids),
+                "token_metas" => token_metas,
+                "tier" => tier
+            },
+        )
+    }
+
+    pub fn sale_item(&self, token_id: &str) -> Option<(Option<String>, Meta)> {
+        self.0.query_dictionary("sale_items", token_key(token_id))
+    }
+
+    pub fn set_mint_price(&self, sender: AccountHash, price: Option<U512>) {
+        self.0
+            .call_contract(sender, "set_mint_price", runtime_args! {"price" => price})
+    }
+
+    pub fn reveal(&self, sender: AccountHash, base_uri: &str, offset_seed: U256) {
+        self.0.call_contract(
+            sender,
+            "reveal",
+            runtime_args! {
+                "base_uri" => base_uri,
+                "offset_seed" => offset_seed
+            },
+        )
+    }
+
+    pub fn reveal_offset(&self) -> Option<U256> {
+        self.0.query_named_key(String::from("reveal_offset"))
+    }
+
+    pub fn burn_one<T: Into<Key>>(&self, sender: AccountHash, owner: T, token_id: &str) {
+        self.0.call_contract(
+            sender,
+            "burn",
+            runtime_args! {
+                "owner" => owner.into(),
+                "token_ids" => to_ids(vec![token_id])
+            },
+        )
+    }
+
+    pub fn transfer<T: Into<Key>>(&self, sender: AccountHash, recipient: T, token_ids: Vec<&str>) {
+        self.0.call_contract(
+            sender,
+            "transfer",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "token_ids" => to_ids(token_ids)
+            },
+        )
+    }
+
+    pub fn transfer_from<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        owner: T,
+        recipient: T,
+        token_ids: Vec<&str>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "transfer_from",
+            runtime_args! {
+                "sender" => owner.into(),
+                "recipient" => recipient.into(),
+                "token_ids" => to_ids(token_ids)
+            },
+        )
+    }
+
+    pub fn approve<T: Into<Key>>(&self, sender: AccountHash, spender: T, token_ids: Vec<&str>) {
+        self.0.call_contract(
+            sender,
+            "approve",
+            runtime_args! {"spender" => spender.into(), "token_ids" => to_ids(token_ids)},
+        )
+    }
+
+    pub fn get_approved<T: Into<Key>>(&self, owner: T, token_id: &str) -> Option<Key> {
+        self.0.query_dictionary(
+            "allowances",
+            key_and_value_to_str::<String>(&owner.into(), &token_id.to_string()),
+        )
+    }
+
+    pub fn attach_child(&self, sender: AccountHash, parent_id: &str, token_ids: Vec<&str>) {
+        self.0.call_contract(
+            sender,
+            "attach_child",
+            runtime_args! {
+                "parent_id" => parent_id.to_string(),
+                "token_ids" => to_ids(token_ids)
+            },
+        )
+    }
+
+    pub fn detach_child(&self, sender: AccountHash, parent_id: &str, token_ids: Vec<&str>) {
+        self.0.call_contract(
+            sender,
+            "detach_child",
+            runtime_args! {
+                "parent_id" => parent_id.to_string(),
+                "token_ids" => to_ids(token_ids)
+            },
+        )
+    }
+
+    pub fn update_token_meta(&self, sender: AccountHash, token_id: &str, token_meta: Meta) {
+        self.0.call_contract(
+            sender,
+            "update_token_meta",
+            runtime_args! {
+                "token_id" => token_id.to_string(),
+                "token_meta" => token_meta
+            },
+        )
+    }
+
+    pub fn set_token_uri(&self, sender: AccountHash, token_id: &str, uri: Option<&str>) {
+        self.0.call_contract(
+            sender,
+            "set_token_uri",
+            runtime_args! {
+                "token_id" => token_id.to_string(),
+                "uri" => uri.map(String::from)
+            },
+        )
+    }
+
+    pub fn token_uri_override(&self, token_id: &str) -> Option<String> {
+        self.0.query_dictionary("token_uris", token_key(token_id))
+    }
+
+    pub fn parent_of(&self, token_id: &str) -> Option<TokenId> {
+        self.0.query_dictionary("parents", token_key(token_id))
+    }
+
+    pub fn children_of(&self, token_id: &str) -> Vec<TokenId> {
+        self.0
+            .query_dictionary("children", token_key(token_id))
+            .unwrap_or_default()
+    }
+
+    pub fn get_token_by_index<T: Into<Key>>(&self, account: T, index: U256) -> Option<TokenId> {
+        self.0.query_dictionary(
+            "owned_tokens_by_index",
+            key_and_value_to_str(&account.into(), &index),
+        )
+    }
+
+    pub fn balance_of<T: Into<Key>>(&self, account: T) -> U256 {
+        self.0
+            .query_dictionary("balances", key_to_str(&account.into()))
+            .unwrap_or_default()
+    }
+
+    pub fn owner_of(&self, token_id: &str) -> Option<Key> {
+        self.0.query_dictionary("owners", token_key(token_id))
+    }
+
+    pub fn token_meta(&self, token_id: &str) -> Option<Meta> {
+        self.0.query_dictionary("metadata", token_key(token_id))
+    }
+
+    pub fn is_burned(&self, token_id: &str) -> bool {
+        self.0
+            .query_dictionary::<Key>("burned_tokens", token_key(token_id))
+            .is_some()
+    }
+
+    pub fn name(&self) -> String {
+        self.0.query_named_key(String::from("name"))
+    }
+
+    pub fn symbol(&self) -> String {
+        self.0.query_named_key(String::from("symbol"))
+    }
+
+    pub fn total_supply(&self) -> U256 {
+        self.0.query_named_key(String::from("total_supply"))
+    }
+
+    pub fn package_key(&self) -> Key {
+        Key::Hash(self.0.package_hash())
+    }
+}
+
+/// Item key the contract stores a string `token_id` under in its token
+/// dictionaries.
+pub fn token_key(token_id: &str) -> String {
+    hex::encode(blake2b256(token_id.as_bytes()))
+}
+
+fn to_ids(token_ids: Vec<&str>) -> Vec<TokenId> {
+    token_ids.into_iter().map(String::from).collect()
+}
@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U256, U512};
+use test_env::TestEnv;
+
+use crate::{
+    cep47_instance::Meta,
+    cep47_string_instance::{token_key, CEP47StringInstance},
+};
+
+const NAME: &str = "DragonsNFT";
+const SYMBOL: &str = "DGNFT";
+
+const UUID: &str = "0f8fad5b-d9cb-469f-a165-70867728950e";
+const HASH: &str = "5feceb66ffc86f38d952786c6d696c79c2dbc239dd4e91b46729d73a27fb57e9";
+
+mod meta {
+    use super::{BTreeMap, Meta};
+
+    pub fn contract_meta() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("origin".to_string(), "fire".to_string());
+        meta
+    }
+
+    pub fn red_dragon() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("color".to_string(), "red".to_string());
+        meta
+    }
+
+    pub fn blue_dragon() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("color".to_string(), "blue".to_string());
+        meta
+    }
+}
+
+fn deploy() -> (TestEnv, CEP47StringInstance, AccountHash) {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let token = CEP47StringInstance::new(&env, NAME, owner, NAME, SYMBOL, meta::contract_meta());
+    (env, token, owner)
+}
+
+#[test]
+fn test_deploy() {
+    let (_, token, _) = deploy();
+    assert_eq!(token.name(), NAME);
+    assert_eq!(token.symbol(), SYMBOL);
+    assert_eq!(token.total_supply(), U256::zero());
+}
+
+#[test]
+fn test_mint_uuid_and_hash_ids() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_many(
+        owner,
+        user,
+        vec![UUID, HASH],
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    assert_eq!(token.total_supply(), U256::from(2));
+    assert_eq!(token.balance_of(user), U256::from(2));
+    assert_eq!(token.owner_of(UUID).unwrap(), Key::Account(user));
+    assert_eq!(token.owner_of(HASH).unwrap(), Key::Account(user));
+    assert_eq!(token.token_meta(UUID).unwrap(), meta::red_dragon());
+    assert_eq!(token.token_meta(HASH).unwrap(), meta::blue_dragon());
+    assert_eq!(
+        token.get_token_by_index(user, U256::one()),
+        Some(HASH.to_string())
+    );
+}
+
+#[test]
+fn test_mint_id_longer_than_item_key_limit() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_id = HASH.repeat(4);
+    token.mint_one(owner, user, &token_id, meta::red_dragon());
+    assert_eq!(token.owner_of(&token_id).unwrap(), Key::Account(user));
+    assert_eq!(token_key(&token_id).len(), 64);
+}
+
+#[test]
+#[should_panic]
+fn test_mint_existing_id() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, UUID, meta::red_dragon());
+    token.mint_one(owner, user, UUID, meta::blue_dragon());
+}
+
+#[test]
+fn test_transfer() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    token.mint_many(
+        owner,
+        ali,
+        vec![UUID, HASH],
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    token.transfer(ali, bob, vec![UUID]);
+    assert_eq!(token.owner_of(UUID).unwrap(), Key::Account(bob));
+    assert_eq!(token.balance_of(ali), U256::one());
+    assert_eq!(
+        token.get_token_by_index(ali, U256::zero()),
+        Some(HASH.to_string())
+    );
+    assert_eq!(
+        token.get_token_by_index(bob, U256::zero()),
+        Some(UUID.to_string())
+    );
+}
+
+#[test]
+fn test_approve_and_transfer_from() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    token.mint_one(owner, ali, UUID, meta::red_dragon());
+    token.approve(ali, bob, vec![UUID]);
+    assert_eq!(token.get_approved(ali, UUID).unwrap(), Key::Account(bob));
+    token.transfer_from(bob, ali, bob, vec![UUID]);
+    assert_eq!(token.owner_of(UUID).unwrap(), Key::Account(bob));
+    assert_eq!(token.get_approved(ali, UUID), None);
+}
+
+#[test]
+fn test_burn() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, UUID, meta::red_dragon());
+    token.burn_one(user, user, UUID);
+    assert_eq!(token.total_supply(), U256::zero());
+    assert_eq!(token.owner_of(UUID), None);
+    assert_eq!(token.token_meta(UUID), None);
+    assert!(token.is_burned(UUID));
+}
+
+#[test]
+fn test_update_token_meta() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, UUID, meta::red_dragon());
+    token.update_token_meta(owner, UUID, meta::blue_dragon());
+    assert_eq!(token.token_meta(UUID).unwrap(), meta::blue_dragon());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(20)")]
+fn test_update_token_meta_by_owner() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, UUID, meta::red_dragon());
+    token.update_token_meta(user, UUID, meta::blue_dragon());
+}
+
+#[test]
+fn test_set_token_uri() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, HASH, meta::red_dragon());
+    token.set_token_uri(owner, HASH, Some("ipfs://dragon"));
+    assert_eq!(
+        token.token_uri_override(HASH),
+        Some("ipfs://dragon".to_string())
+    );
+    token.set_token_uri(owner, HASH, None);
+    assert_eq!(token.token_uri_override(HASH), None);
+}
+
+#[test]
+fn test_attach_and_detach_child() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_many(
+        owner,
+        user,
+        vec![UUID, HASH],
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    token.attach_child(user, UUID, vec![HASH]);
+    assert_eq!(token.parent_of(HASH), Some(UUID.to_string()));
+    assert_eq!(token.children_of(UUID), vec![HASH.to_string()]);
+    assert_eq!(token.owner_of(HASH).unwrap(), token.package_key());
+
+    token.detach_child(user, UUID, vec![HASH]);
+    assert_eq!(token.parent_of(HASH), None);
+    assert!(token.children_of(UUID).is_empty());
+    assert_eq!(token.owner_of(HASH).unwrap(), Key::Account(user));
+}
+
+#[test]
+#[should_panic]
+fn test_attach_child_to_itself() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, UUID, meta::red_dragon());
+    token.attach_child(user, UUID, vec![UUID]);
+}
+
+#[test]
+fn test_mint_copies() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_copies(owner, user, vec![UUID, HASH], meta::red_dragon(), 2);
+    assert_eq!(token.balance_of(user), U256::from(2));
+    assert_eq!(token.token_meta(UUID).unwrap(), meta::red_dragon());
+    assert_eq!(token.token_meta(HASH).unwrap(), meta::red_dragon());
+}
+
+#[test]
+fn test_purchase_mint_listed_items() {
+    let (env, token, owner) = deploy();
+    let buyer = env.next_user();
+    let price = U512::from(5_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+    token.list_sale_items(
+        owner,
+        vec![UUID, HASH],
+        vec![meta::red_dragon(), meta::blue_dragon()],
+        None,
+    );
+    assert_eq!(token.sale_item(UUID), Some((None, meta::red_dragon())));
+
+    token.purchase_mint(buyer, buyer, vec![UUID, HASH], price * 2);
+    assert_eq!(token.owner_of(UUID).unwrap(), Key::Account(buyer));
+    assert_eq!(token.token_meta(HASH).unwrap(), meta::blue_dragon());
+    assert_eq!(token.sale_item(UUID), None);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(30)")]
+fn test_purchase_mint_unlisted_token() {
+    let (env, token, owner) = deploy();
+    let buyer = env.next_user();
+    let price = U512::from(5_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+    token.purchase_mint(buyer, buyer, vec![UUID], price);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(19)")]
+fn test_remint_burned_token_when_forbidden() {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let user = env.next_user();
+    let token = CEP47StringInstance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! { "forbid_remint" => true },
+    );
+    token.mint_one(owner, user, UUID, meta::red_dragon());
+    token.burn_one(user, user, UUID);
+    token.mint_one(owner, user, UUID, meta::blue_dragon());
+}
+
+#[test]
+fn test_reveal() {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let user = env.next_user();
+    let token = CEP47StringInstance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! {
+            "provenance_hash" => HASH.to_string(),
+            "placeholder_meta" => meta::blue_dragon()
+        },
+    );
+    token.mint_many(
+        owner,
+        user,
+        vec![UUID, HASH],
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    token.reveal(owner, "ipfs://dragons/", U256::from(7));
+    assert_eq!(token.reveal_offset(), Some(U256::one()));
+}
@@ -1,9 +1,13 @@
 use std::collections::BTreeMap;
 
-use casper_types::{account::AccountHash, Key, U256};
+use casper_types::{
+    account::AccountHash, runtime_args, AccessRights, Key, RuntimeArgs, URef, U256, U512,
+};
 use test_env::TestEnv;
 
-use crate::cep47_instance::{CEP47Instance, Meta, TokenId};
+use crate::cep47_instance::{
+    blake2b256, merkle_parent, meta_commitment, token_key, CEP47Instance, Checksum, Meta, TokenId,
+};
 
 const NAME: &str = "DragonsNFT";
 const SYMBOL: &str = "DGNFT";
@@ -48,6 +52,21 @@ fn deploy() -> (TestEnv, CEP47Instance, AccountHash) {
     (env, token, owner)
 }
 
+fn deploy_with_burn_policy(burn_policy: u8) -> (TestEnv, CEP47Instance, AccountHash) {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let token = CEP47Instance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! { "burn_policy" => burn_policy },
+    );
+    (env, token, owner)
+}
+
 #[test]
 fn test_deploy() {
     let (_, token, _) = deploy();
@@ -57,6 +76,162 @@ fn test_deploy() {
     assert_eq!(token.total_supply(), U256::zero());
 }
 
+#[test]
+fn test_upgrade_keeps_state() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_id = TokenId::zero();
+    token.mint_one(owner, user, token_id, meta::red_dragon());
+    let old_contract_hash = token.contract_hash();
+
+    token.upgrade();
+    assert_ne!(token.contract_hash(), old_contract_hash);
+    assert_eq!(token.storage_version(), 16);
+    assert_eq!(token.name(), NAME);
+    assert_eq!(token.total_supply(), U256::one());
+    assert_eq!(token.owner_of(token_id).unwrap(), Key::Account(user));
+    assert_eq!(token.token_meta(token_id).unwrap(), meta::red_dragon());
+
+    token.mint_one(owner, user, TokenId::one(), meta::gold_dragon());
+    assert_eq!(token.balance_of(Key::Account(user)), U256::from(2));
+}
+
+#[test]
+fn test_upgrade_from_v1_layout() {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    let token = CEP47Instance::new_v1(&env, NAME, owner, NAME, SYMBOL, meta::contract_meta());
+    token.mint_many(
+        owner,
+        ali,
+        vec![TokenId::zero(), TokenId::one()],
+        vec![meta::red_dragon(), meta::gold_dragon()],
+    );
+    assert_eq!(token.storage_version(), 1);
+
+    token.upgrade();
+    assert_eq!(token.storage_version(), 16);
+    assert_eq!(token.name(), NAME);
+    assert_eq!(token.total_supply(), U256::from(2));
+
+    // Items under decimal ids and plain account keys are still found.
+    token.transfer(ali, bob, vec![TokenId::one()]);
+
+    token.migrate_token_keys(owner, vec![TokenId::zero(), TokenId::one()]);
+    token.migrate_account_keys(owner, vec![Key::Account(ali), Key::Account(bob)]);
+    assert_eq!(token.owner_of(TokenId::zero()).unwrap(), Key::Account(ali));
+    assert_eq!(token.owner_of(TokenId::one()).unwrap(), Key::Account(bob));
+    assert_eq!(
+        token.token_meta(TokenId::zero()).unwrap(),
+        meta::red_dragon()
+    );
+    assert_eq!(token.balance_of(Key::Account(ali)), U256::one());
+    assert_eq!(token.balance_of(Key::Account(bob)), U256::one());
+    assert_eq!(
+        token.get_token_by_index(Key::Account(ali), U256::zero()),
+        Some(TokenId::zero())
+    );
+
+    // The upgrader is now an admin, and storage added after layout 1 is set up.
+    token.mint_one(owner, ali, TokenId::from(2), meta::blue_dragon());
+    token.attach_child(ali, TokenId::zero(), vec![TokenId::from(2)]);
+    assert_eq!(token.parent_of(TokenId::from(2)), Some(TokenId::zero()));
+    token.burn_one(bob, bob, TokenId::one());
+    assert!(token.is_burned(TokenId::one()));
+    assert_eq!(token.total_supply(), U256::from(2));
+}
+
+#[test]
+fn test_token_ids_near_max() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    let token_ids = vec![U256::MAX, U256::MAX - 1];
+    token.mint_many(
+        owner,
+        ali,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::gold_dragon()],
+    );
+    assert_eq!(token.owner_of(U256::MAX).unwrap(), Key::Account(ali));
+    assert_eq!(
+        token.token_meta(U256::MAX - 1).unwrap(),
+        meta::gold_dragon()
+    );
+
+    token.transfer(ali, bob, vec![U256::MAX]);
+    assert_eq!(token.owner_of(U256::MAX).unwrap(), Key::Account(bob));
+    assert_eq!(
+        token.get_token_by_index(Key::Account(bob), U256::zero()),
+        Some(U256::MAX)
+    );
+
+    token.burn_one(ali, ali, U256::MAX - 1);
+    assert_eq!(token.owner_of(U256::MAX - 1), None);
+    assert!(token.is_burned(U256::MAX - 1));
+}
+
+#[test]
+fn test_token_key_is_fixed_length() {
+    assert_eq!(token_key(TokenId::zero()).len(), 64);
+    assert_eq!(token_key(U256::MAX).len(), 64);
+    assert_ne!(token_key(TokenId::one()), token_key(U256::from(16)));
+}
+
+#[test]
+#[should_panic]
+fn test_migrate_token_keys_by_non_admin() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.migrate_token_keys(user, vec![TokenId::zero()]);
+}
+
+#[test]
+fn test_update_collection_metadata() {
+    let (_, token, owner) = deploy();
+    token.set_name(owner, "WyvernsNFT");
+    token.set_symbol(owner, "WVNFT");
+    assert_eq!(token.name(), "WyvernsNFT");
+    assert_eq!(token.symbol(), "WVNFT");
+
+    let mut set = BTreeMap::new();
+    set.insert("website".to_string(), "https://wyverns.example".to_string());
+    token.update_meta_keys(owner, set, vec![]);
+    let mut expected = meta::contract_meta();
+    expected.insert("website".to_string(), "https://wyverns.example".to_string());
+    assert_eq!(token.meta(), expected);
+
+    token.set_meta(owner, BTreeMap::new());
+    assert!(token.meta().is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_set_name_by_non_admin() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.set_name(user, "WyvernsNFT");
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(28)")]
+fn test_set_meta_when_locked() {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let token = CEP47Instance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! { "lock_collection_meta" => true },
+    );
+    token.set_meta(owner, BTreeMap::new());
+}
+
 #[test]
 fn test_token_meta() {
     let (env, token, owner) = deploy();
@@ -254,6 +429,276 @@ fn test_burn_one() {
     assert_eq!(new_second_user_token, None);
 }
 
+#[test]
+fn test_default_burn_policy() {
+    let (_, token, _) = deploy();
+    assert_eq!(token.burn_policy(), 1);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(1)")]
+fn test_burn_as_admin_with_default_policy() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.burn_as_admin(owner, user, vec![TokenId::zero()]);
+}
+
+#[test]
+fn test_burn_with_owner_only_policy() {
+    let (env, token, owner) = deploy_with_burn_policy(0);
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.burn_one(user, user, TokenId::zero());
+    assert_eq!(token.total_supply(), U256::zero());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(1)")]
+fn test_burn_from_allowance_with_owner_only_policy() {
+    let (env, token, owner) = deploy_with_burn_policy(0);
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.approve(user, owner, vec![TokenId::zero()]);
+    token.burn_one(owner, user, TokenId::zero());
+}
+
+#[test]
+fn test_burn_as_admin_with_admin_policy() {
+    let (env, token, owner) = deploy_with_burn_policy(2);
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.burn_as_admin(owner, user, vec![TokenId::zero()]);
+    assert_eq!(token.owner_of(TokenId::zero()), None);
+    assert_eq!(token.total_supply(), U256::zero());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(1)")]
+fn test_burn_by_owner_with_admin_policy() {
+    let (env, token, owner) = deploy_with_burn_policy(2);
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.burn_one(user, user, TokenId::zero());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(20)")]
+fn test_burn_as_admin_by_non_admin() {
+    let (env, token, owner) = deploy_with_burn_policy(2);
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.burn_as_admin(user, user, vec![TokenId::zero()]);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(18)")]
+fn test_burn_with_disabled_policy() {
+    let (env, token, owner) = deploy_with_burn_policy(3);
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.burn_one(user, user, TokenId::zero());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(18)")]
+fn test_burn_as_admin_with_disabled_policy() {
+    let (env, token, owner) = deploy_with_burn_policy(3);
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.burn_as_admin(owner, user, vec![TokenId::zero()]);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(6)")]
+fn test_burn_as_admin_attached_child() {
+    let (env, token, owner) = deploy_with_burn_policy(2);
+    let user = env.next_user();
+    token.mint_many(
+        owner,
+        user,
+        vec![TokenId::zero(), TokenId::one()],
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    token.attach_child(user, TokenId::zero(), vec![TokenId::one()]);
+    token.burn_as_admin(owner, token.package_key(), vec![TokenId::one()]);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(8)")]
+fn test_burn_as_admin_parent_with_children() {
+    let (env, token, owner) = deploy_with_burn_policy(2);
+    let user = env.next_user();
+    token.mint_many(
+        owner,
+        user,
+        vec![TokenId::zero(), TokenId::one()],
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    token.attach_child(user, TokenId::zero(), vec![TokenId::one()]);
+    token.burn_as_admin(owner, user, vec![TokenId::zero()]);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(2)")]
+fn test_deploy_with_unknown_burn_policy() {
+    deploy_with_burn_policy(4);
+}
+
+#[test]
+fn test_burn_leaves_tombstone() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.mint_many(
+        owner,
+        user,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::gold_dragon()],
+    );
+    assert!(!token.is_burned(token_ids[0]));
+
+    token.burn_many(user, user, token_ids.clone());
+    assert!(token.is_burned(token_ids[0]));
+    assert!(token.is_burned(token_ids[1]));
+    assert_eq!(token.burned_supply(), U256::from(2));
+}
+
+#[test]
+fn test_remint_burned_token() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.burn_one(user, user, TokenId::zero());
+
+    token.mint_one(owner, user, TokenId::zero(), meta::blue_dragon());
+    assert!(!token.is_burned(TokenId::zero()));
+    assert_eq!(token.owner_of(TokenId::zero()).unwrap(), Key::Account(user));
+    assert_eq!(token.burned_supply(), U256::one());
+}
+
+#[test]
+#[should_panic]
+fn test_remint_burned_token_when_forbidden() {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let user = env.next_user();
+    let token = CEP47Instance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! { "forbid_remint" => true },
+    );
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.burn_one(user, user, TokenId::zero());
+    token.mint_one(owner, user, TokenId::zero(), meta::blue_dragon());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(29)")]
+fn test_mint_to_uref() {
+    let (_, token, owner) = deploy();
+    let purse = URef::new([7u8; 32], AccessRights::READ_ADD_WRITE);
+    token.mint_one(owner, Key::URef(purse), TokenId::zero(), meta::red_dragon());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(29)")]
+fn test_transfer_to_balance_key() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.transfer(user, Key::Balance([7u8; 32]), vec![TokenId::zero()]);
+}
+
+#[test]
+fn test_balance_of_uref() {
+    let (_, token, owner) = deploy();
+    let purse = Key::URef(URef::new([7u8; 32], AccessRights::READ_ADD_WRITE));
+    token.call_balance_of(owner, purse);
+    assert_eq!(token.balance_of(purse), U256::zero());
+}
+
+#[test]
+fn test_account_and_hash_balances_are_separate() {
+    let (_, token, owner) = deploy();
+    let account = Key::Account(AccountHash::new([7u8; 32]));
+    let hash = Key::Hash([7u8; 32]);
+    token.mint_one(owner, account, TokenId::zero(), meta::red_dragon());
+    token.mint_one(owner, hash, TokenId::one(), meta::gold_dragon());
+    assert_eq!(token.balance_of(account), U256::one());
+    assert_eq!(token.balance_of(hash), U256::one());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(20)")]
+fn test_migrate_account_keys_by_non_admin() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.migrate_account_keys(user, vec![Key::Account(user)]);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(29)")]
+fn test_transfer_to_same_owner() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.transfer(user, user, vec![TokenId::zero()]);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(29)")]
+fn test_transfer_to_token_contract() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.transfer(user, token.package_key(), vec![TokenId::zero()]);
+}
+
+fn deploy_for_accounts_only() -> (TestEnv, CEP47Instance, AccountHash) {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let token = CEP47Instance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! { "recipient_policy" => 0u8 },
+    );
+    (env, token, owner)
+}
+
+#[test]
+fn test_accounts_only_recipient_policy() {
+    let (env, token, owner) = deploy_for_accounts_only();
+    let user = env.next_user();
+    assert_eq!(token.recipient_policy(), 0);
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.transfer(user, owner, vec![TokenId::zero()]);
+    assert_eq!(
+        token.owner_of(TokenId::zero()).unwrap(),
+        Key::Account(owner)
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(29)")]
+fn test_mint_to_contract_with_accounts_only_policy() {
+    let (_, token, owner) = deploy_for_accounts_only();
+    token.mint_one(
+        owner,
+        Key::Hash([7u8; 32]),
+        TokenId::zero(),
+        meta::red_dragon(),
+    );
+}
+
 #[test]
 fn test_transfer_token() {
     let (env, token, owner) = deploy();
@@ -387,26 +832,1079 @@ fn test_token_metadata_update() {
     assert_eq!(token.token_meta(token_id).unwrap(), meta::gold_dragon());
 }
 
+#[test]
+fn test_update_token_meta_keys() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_id = TokenId::zero();
+    let mut dragon = meta::red_dragon();
+    dragon.insert("name".to_string(), "Smaug".to_string());
+    dragon.insert("age".to_string(), "171".to_string());
+    token.mint_one(owner, user, token_id, dragon);
+
+    let mut set = BTreeMap::new();
+    set.insert("color".to_string(), "gold".to_string());
+    set.insert("lair".to_string(), "Erebor".to_string());
+    token.update_token_meta_keys(owner, token_id, set, vec!["age".to_string()]);
+
+    let mut expected = meta::gold_dragon();
+    expected.insert("name".to_string(), "Smaug".to_string());
+    expected.insert("lair".to_string(), "Erebor".to_string());
+    assert_eq!(token.token_meta(token_id).unwrap(), expected);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(20)")]
+fn test_update_token_meta_by_non_admin() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.update_token_meta(user, TokenId::zero(), meta::gold_dragon());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(20)")]
+fn test_update_token_meta_keys_by_non_admin() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.update_token_meta_keys(user, TokenId::zero(), meta::gold_dragon(), vec![]);
+}
+
 #[test]
 #[should_panic]
-fn test_transfer_from_tokens_after_revoked_approval() {
+fn test_update_token_meta_keys_setting_and_removing_a_key() {
     let (env, token, owner) = deploy();
-    let ali = env.next_user();
-    let bob = env.next_user();
-    let token_metas = vec![meta::red_dragon(), meta::gold_dragon()];
-    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.update_token_meta_keys(
+        owner,
+        TokenId::zero(),
+        meta::gold_dragon(),
+        vec!["color".to_string()],
+    );
+}
 
-    token.mint_many(owner, ali, token_ids.clone(), token_metas);
-    assert_eq!(token.total_supply(), U256::from(2));
-    assert_eq!(token.balance_of(Key::Account(ali)), U256::from(2));
-    assert_eq!(token.owner_of(token_ids[0]).unwrap(), Key::Account(ali));
-    assert_eq!(token.owner_of(token_ids[1]).unwrap(), Key::Account(ali));
-    token.approve(ali, bob, vec![TokenId::one()]);
+#[test]
+fn test_token_meta_history() {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let user = env.next_user();
+    let token = CEP47Instance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! { "keep_meta_history" => true },
+    );
+    let token_id = TokenId::zero();
+    token.mint_one(owner, user, token_id, meta::red_dragon());
+    assert_eq!(token.token_meta_version(token_id), 0);
+
+    token.update_token_meta(owner, token_id, meta::gold_dragon());
+    token.update_token_meta(owner, token_id, meta::blue_dragon());
+    assert_eq!(token.token_meta_version(token_id), 2);
     assert_eq!(
-        token.get_approved(ali, token_ids[1]).unwrap(),
-        Key::Account(bob)
+        token.token_meta_history(token_id, 0).unwrap(),
+        meta::red_dragon()
     );
-    token.revoke(ali, vec![TokenId::one()]);
+    assert_eq!(
+        token.token_meta_history(token_id, 1).unwrap(),
+        meta::gold_dragon()
+    );
+    assert_eq!(token.token_meta(token_id).unwrap(), meta::blue_dragon());
+}
 
-    token.transfer_from(bob, ali, owner, vec![TokenId::one()]);
+#[test]
+fn test_remint_starts_meta_history_over() {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let user = env.next_user();
+    let token = CEP47Instance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! { "keep_meta_history" => true },
+    );
+    let token_id = TokenId::zero();
+    token.mint_one(owner, user, token_id, meta::red_dragon());
+    token.update_token_meta(owner, token_id, meta::gold_dragon());
+    token.update_token_meta(owner, token_id, meta::blue_dragon());
+    token.burn_one(user, user, token_id);
+    assert_eq!(token.token_meta_history(token_id, 0), None);
+    assert_eq!(token.token_meta_history(token_id, 1), None);
+
+    token.mint_one(owner, user, token_id, meta::gold_dragon());
+    token.update_token_meta(owner, token_id, meta::red_dragon());
+    assert_eq!(token.token_meta_version(token_id), 1);
+    assert_eq!(
+        token.token_meta_history(token_id, 0).unwrap(),
+        meta::gold_dragon()
+    );
+    assert_eq!(token.token_meta_history(token_id, 1), None);
+}
+
+#[test]
+fn test_token_meta_without_history() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_id = TokenId::zero();
+    token.mint_one(owner, user, token_id, meta::red_dragon());
+    token.update_token_meta(owner, token_id, meta::gold_dragon());
+    assert_eq!(token.token_meta_version(token_id), 1);
+    assert_eq!(token.token_meta_history(token_id, 0), None);
+}
+
+#[test]
+fn test_remint_burned_frozen_token() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_id = TokenId::zero();
+    token.mint_one(owner, user, token_id, meta::red_dragon());
+    token.freeze_token_meta(owner, vec![token_id]);
+    token.burn_one(user, user, token_id);
+    assert!(!token.is_meta_frozen(token_id));
+
+    token.mint_one(owner, user, token_id, meta::blue_dragon());
+    token.update_token_meta(owner, token_id, meta::gold_dragon());
+    assert_eq!(token.token_meta(token_id).unwrap(), meta::gold_dragon());
+}
+
+#[test]
+fn test_freeze_token_meta() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.mint_many(
+        owner,
+        user,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+
+    token.freeze_token_meta(owner, vec![token_ids[0]]);
+    assert!(token.is_meta_frozen(token_ids[0]));
+    assert!(!token.is_meta_frozen(token_ids[1]));
+
+    token.update_token_meta(owner, token_ids[1], meta::gold_dragon());
+    assert_eq!(token.token_meta(token_ids[1]).unwrap(), meta::gold_dragon());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(21)")]
+fn test_update_frozen_token_meta() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.freeze_token_meta(owner, vec![TokenId::zero()]);
+    token.update_token_meta(owner, TokenId::zero(), meta::gold_dragon());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(20)")]
+fn test_freeze_token_meta_by_non_admin() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.freeze_token_meta(user, vec![TokenId::zero()]);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(21)")]
+fn test_update_token_meta_after_freeze_all() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.freeze_all_meta(owner);
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    assert!(token.is_meta_frozen(TokenId::zero()));
+    token.update_token_meta(owner, TokenId::zero(), meta::gold_dragon());
+}
+
+#[test]
+fn test_base_uri() {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let token = CEP47Instance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! { "base_uri" => "ipfs://dragons/" },
+    );
+    assert_eq!(token.base_uri(), "ipfs://dragons/");
+
+    token.set_base_uri(owner, "ar://dragons/");
+    assert_eq!(token.base_uri(), "ar://dragons/");
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(20)")]
+fn test_set_base_uri_by_non_admin() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.set_base_uri(user, "ipfs://dragons/");
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(21)")]
+fn test_set_base_uri_after_freeze_all() {
+    let (_, token, owner) = deploy();
+    token.freeze_all_meta(owner);
+    token.set_base_uri(owner, "ipfs://dragons/");
+}
+
+#[test]
+fn test_token_uri_override() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), BTreeMap::new());
+    assert_eq!(token.token_uri_override(TokenId::zero()), None);
+
+    token.set_token_uri(owner, TokenId::zero(), Some("ipfs://smaug"));
+    assert_eq!(
+        token.token_uri_override(TokenId::zero()).unwrap(),
+        "ipfs://smaug"
+    );
+
+    token.set_token_uri(owner, TokenId::zero(), None);
+    assert_eq!(token.token_uri_override(TokenId::zero()), None);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(21)")]
+fn test_set_token_uri_of_frozen_token() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), BTreeMap::new());
+    token.freeze_token_meta(owner, vec![TokenId::zero()]);
+    token.set_token_uri(owner, TokenId::zero(), Some("ipfs://smaug"));
+}
+
+fn checksum(content: &[u8]) -> Checksum {
+    ("blake2b-256".to_string(), hex::encode(blake2b256(content)))
+}
+
+#[test]
+fn test_mint_with_checksums() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_with_checksums(
+        owner,
+        user,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+        vec![checksum(b"red dragon")],
+    );
+    assert_eq!(
+        token.token_checksum(TokenId::zero()).unwrap(),
+        checksum(b"red dragon")
+    );
+
+    // Updating the metadata alone keeps the checksum.
+    token.update_token_meta(owner, TokenId::zero(), meta::gold_dragon());
+    assert_eq!(
+        token.token_checksum(TokenId::zero()).unwrap(),
+        checksum(b"red dragon")
+    );
+
+    token.update_token_meta_and_checksum(
+        owner,
+        TokenId::zero(),
+        meta::blue_dragon(),
+        checksum(b"blue dragon"),
+    );
+    assert_eq!(
+        token.token_meta(TokenId::zero()).unwrap(),
+        meta::blue_dragon()
+    );
+    assert_eq!(
+        token.token_checksum(TokenId::zero()).unwrap(),
+        checksum(b"blue dragon")
+    );
+
+    token.burn_one(user, user, TokenId::zero());
+    assert_eq!(token.token_checksum(TokenId::zero()), None);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(26)")]
+fn test_mint_with_invalid_checksum() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_with_checksums(
+        owner,
+        user,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+        vec![("md5".to_string(), "00".repeat(16))],
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(26)")]
+fn test_update_token_meta_with_short_checksum() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.update_token_meta_and_checksum(
+        owner,
+        TokenId::zero(),
+        meta::blue_dragon(),
+        ("sha2-256".to_string(), "00".repeat(16)),
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(20)")]
+fn test_update_token_meta_and_checksum_by_non_admin() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.update_token_meta_and_checksum(
+        user,
+        TokenId::zero(),
+        meta::blue_dragon(),
+        checksum(b"blue dragon"),
+    );
+}
+
+fn deploy_unrevealed() -> (TestEnv, CEP47Instance, AccountHash) {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let token = CEP47Instance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! {
+            "provenance_hash" => hex::encode(blake2b256(b"dragons")),
+            "placeholder_meta" => meta::black_dragon()
+        },
+    );
+    (env, token, owner)
+}
+
+#[test]
+fn test_reveal() {
+    let (env, token, owner) = deploy_unrevealed();
+    let user = env.next_user();
+    assert_eq!(
+        token.provenance_hash().unwrap(),
+        hex::encode(blake2b256(b"dragons"))
+    );
+    assert_eq!(token.placeholder_meta().unwrap(), meta::black_dragon());
+    assert_eq!(token.reveal_offset(), None);
+
+    token.mint_many(
+        owner,
+        user,
+        vec![TokenId::zero(), TokenId::one(), TokenId::from(2)],
+        vec![meta::red_dragon(), meta::blue_dragon(), meta::gold_dragon()],
+    );
+    token.reveal(owner, "ipfs://dragons/", U256::from(7));
+    assert_eq!(token.reveal_offset().unwrap(), U256::one());
+    assert_eq!(token.base_uri(), "ipfs://dragons/");
+}
+
+#[test]
+fn test_mint_before_reveal_stores_commitment() {
+    let (env, token, owner) = deploy_unrevealed();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    assert_eq!(token.token_meta(TokenId::zero()), None);
+    assert_eq!(
+        token.meta_commitment(TokenId::zero()),
+        Some(meta_commitment(&meta::red_dragon()))
+    );
+}
+
+#[test]
+fn test_reveal_token_meta_with_offset() {
+    let (env, token, owner) = deploy_unrevealed();
+    let user = env.next_user();
+    let token_ids = vec![TokenId::zero(), TokenId::one(), TokenId::from(2)];
+    token.mint_many(
+        owner,
+        user,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::blue_dragon(), meta::gold_dragon()],
+    );
+    token.reveal(owner, "ipfs://dragons/", U256::from(7));
+
+    // The offset is one, so every token shows the next token's metadata.
+    token.reveal_token_meta(
+        owner,
+        token_ids,
+        vec![meta::blue_dragon(), meta::gold_dragon(), meta::red_dragon()],
+    );
+    assert_eq!(
+        token.token_meta(TokenId::zero()).unwrap(),
+        meta::blue_dragon()
+    );
+    assert_eq!(
+        token.token_meta(TokenId::from(2)).unwrap(),
+        meta::red_dragon()
+    );
+    assert_eq!(token.meta_commitment(TokenId::one()), None);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(32)")]
+fn test_reveal_token_meta_not_committed() {
+    let (env, token, owner) = deploy_unrevealed();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.reveal(owner, "ipfs://dragons/", U256::zero());
+    token.reveal_token_meta(owner, vec![TokenId::zero()], vec![meta::gold_dragon()]);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(31)")]
+fn test_reveal_token_meta_before_reveal() {
+    let (env, token, owner) = deploy_unrevealed();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.reveal_token_meta(owner, vec![TokenId::zero()], vec![meta::red_dragon()]);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(31)")]
+fn test_update_token_meta_before_reveal() {
+    let (env, token, owner) = deploy_unrevealed();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.update_token_meta(owner, TokenId::zero(), meta::gold_dragon());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(21)")]
+fn test_reveal_after_freezing_all_meta() {
+    let (_, token, owner) = deploy_unrevealed();
+    token.freeze_all_meta(owner);
+    token.reveal(owner, "ipfs://dragons/", U256::zero());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(27)")]
+fn test_reveal_twice() {
+    let (_, token, owner) = deploy_unrevealed();
+    token.reveal(owner, "ipfs://dragons/", U256::zero());
+    token.reveal(owner, "ipfs://dragons/", U256::zero());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(20)")]
+fn test_reveal_by_non_admin() {
+    let (env, token, _) = deploy_unrevealed();
+    let user = env.next_user();
+    token.reveal(user, "ipfs://dragons/", U256::zero());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(27)")]
+fn test_reveal_without_placeholder() {
+    let (_, token, owner) = deploy();
+    token.reveal(owner, "ipfs://dragons/", U256::zero());
+}
+
+fn deploy_with_meta_schema() -> (TestEnv, CEP47Instance, AccountHash) {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let token = CEP47Instance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! {
+            "meta_required_keys" => vec!["color".to_string()],
+            "meta_allowed_keys" => vec!["color".to_string(), "name".to_string()],
+            "meta_max_value_length" => 8u32
+        },
+    );
+    (env, token, owner)
+}
+
+#[test]
+fn test_mint_with_meta_schema() {
+    let (env, token, owner) = deploy_with_meta_schema();
+    let user = env.next_user();
+    let mut named_dragon = meta::red_dragon();
+    named_dragon.insert("name".to_string(), "Smaug".to_string());
+    token.mint_one(owner, user, TokenId::zero(), named_dragon.clone());
+    token.mint_copies(owner, user, vec![TokenId::one()], meta::gold_dragon(), 1);
+    token.update_token_meta(owner, TokenId::one(), named_dragon.clone());
+    assert_eq!(token.token_meta(TokenId::one()).unwrap(), named_dragon);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(22)")]
+fn test_mint_without_required_meta_key() {
+    let (env, token, owner) = deploy_with_meta_schema();
+    let user = env.next_user();
+    let mut meta = BTreeMap::new();
+    meta.insert("name".to_string(), "Smaug".to_string());
+    token.mint_one(owner, user, TokenId::zero(), meta);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(23)")]
+fn test_mint_copies_with_unknown_meta_key() {
+    let (env, token, owner) = deploy_with_meta_schema();
+    let user = env.next_user();
+    let mut meta = meta::red_dragon();
+    meta.insert("size".to_string(), "huge".to_string());
+    token.mint_copies(owner, user, vec![TokenId::zero()], meta, 1);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(24)")]
+fn test_update_token_meta_with_too_long_value() {
+    let (env, token, owner) = deploy_with_meta_schema();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    let mut meta = BTreeMap::new();
+    meta.insert("color".to_string(), "ultramarine".to_string());
+    token.update_token_meta(owner, TokenId::zero(), meta);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(25)")]
+fn test_mint_with_too_large_meta() {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let user = env.next_user();
+    let token = CEP47Instance::new_with_args(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        runtime_args! {
+            "meta_max_total_size" => 12u32
+        },
+    );
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    let mut named_dragon = meta::red_dragon();
+    named_dragon.insert("name".to_string(), "Smaug".to_string());
+    token.mint_one(owner, user, TokenId::one(), named_dragon);
+}
+
+#[test]
+#[should_panic]
+fn test_transfer_from_tokens_after_revoked_approval() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    let token_metas = vec![meta::red_dragon(), meta::gold_dragon()];
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+
+    token.mint_many(owner, ali, token_ids.clone(), token_metas);
+    assert_eq!(token.total_supply(), U256::from(2));
+    assert_eq!(token.balance_of(Key::Account(ali)), U256::from(2));
+    assert_eq!(token.owner_of(token_ids[0]).unwrap(), Key::Account(ali));
+    assert_eq!(token.owner_of(token_ids[1]).unwrap(), Key::Account(ali));
+    token.approve(ali, bob, vec![TokenId::one()]);
+    assert_eq!(
+        token.get_approved(ali, token_ids[1]).unwrap(),
+        Key::Account(bob)
+    );
+    token.revoke(ali, vec![TokenId::one()]);
+
+    token.transfer_from(bob, ali, owner, vec![TokenId::one()]);
+}
+
+#[test]
+fn test_attach_and_detach_child() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    let token_metas = vec![meta::red_dragon(), meta::gold_dragon(), meta::blue_dragon()];
+    let token_ids = vec![TokenId::zero(), TokenId::one(), TokenId::from(2)];
+    token.mint_many(owner, ali, token_ids.clone(), token_metas);
+
+    token.attach_child(ali, token_ids[0], vec![token_ids[1]]);
+    token.attach_child(ali, token_ids[1], vec![token_ids[2]]);
+    assert_eq!(token.children_of(token_ids[0]), vec![token_ids[1]]);
+    assert_eq!(token.parent_of(token_ids[2]), Some(token_ids[1]));
+    assert_eq!(token.root_owner_of(token_ids[2]), Some(Key::Account(ali)));
+    assert_eq!(token.balance_of(Key::Account(ali)), U256::one());
+
+    token.transfer(ali, bob, vec![token_ids[0]]);
+    assert_eq!(token.root_owner_of(token_ids[2]), Some(Key::Account(bob)));
+
+    token.detach_child(bob, token_ids[1], vec![token_ids[2]]);
+    assert_eq!(token.parent_of(token_ids[2]), None);
+    assert!(token.children_of(token_ids[1]).is_empty());
+    assert_eq!(token.owner_of(token_ids[2]), Some(Key::Account(bob)));
+    assert_eq!(token.balance_of(Key::Account(bob)), U256::from(2));
+}
+
+#[test]
+#[should_panic]
+fn test_transfer_attached_child() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    let token_metas = vec![meta::red_dragon(), meta::gold_dragon()];
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.mint_many(owner, ali, token_ids.clone(), token_metas);
+
+    token.attach_child(ali, token_ids[0], vec![token_ids[1]]);
+    token.transfer(ali, bob, vec![token_ids[1]]);
+}
+
+#[test]
+#[should_panic]
+fn test_attach_parent_to_own_child() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let token_metas = vec![meta::red_dragon(), meta::gold_dragon()];
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.mint_many(owner, ali, token_ids.clone(), token_metas);
+
+    token.attach_child(ali, token_ids[0], vec![token_ids[1]]);
+    token.attach_child(ali, token_ids[1], vec![token_ids[0]]);
+}
+
+#[test]
+#[should_panic]
+fn test_mint_by_non_admin() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.mint_one(user, user, TokenId::zero(), meta::red_dragon());
+}
+
+#[test]
+fn test_purchase_mint() {
+    let (env, token, owner) = deploy();
+    let buyer = env.next_user();
+    let treasury = env.next_user();
+    let price = U512::from(5_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+    assert_eq!(token.mint_price(), Some(price));
+
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.list_sale_items(
+        owner,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::gold_dragon()],
+        None,
+    );
+    token.purchase_mint(buyer, buyer, token_ids.clone(), price * U512::from(2));
+    assert_eq!(token.owner_of(token_ids[0]).unwrap(), Key::Account(buyer));
+    assert_eq!(token.balance_of(Key::Account(buyer)), U256::from(2));
+
+    let treasury_balance = env.account_balance(treasury);
+    token.set_treasury(owner, treasury);
+    token.withdraw(owner, price * U512::from(2));
+    assert_eq!(
+        env.account_balance(treasury),
+        treasury_balance + price * U512::from(2)
+    );
+}
+
+#[test]
+fn test_purchase_mint_with_tier() {
+    let (env, token, owner) = deploy();
+    let buyer = env.next_user();
+    let price = U512::from(20_000_000_000u64);
+    token.set_tier_price(owner, "legendary", Some(price));
+
+    token.list_sale_items(
+        owner,
+        vec![TokenId::zero()],
+        vec![meta::gold_dragon()],
+        Some("legendary".to_string()),
+    );
+    token.purchase_mint(buyer, buyer, vec![TokenId::zero()], price);
+    assert_eq!(
+        token.owner_of(TokenId::zero()).unwrap(),
+        Key::Account(buyer)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_purchase_mint_with_wrong_amount() {
+    let (env, token, owner) = deploy();
+    let buyer = env.next_user();
+    let price = U512::from(5_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+    token.purchase_mint(buyer, buyer, vec![TokenId::zero()], price - U512::one());
+}
+
+#[test]
+#[should_panic]
+fn test_purchase_mint_before_sale() {
+    let (env, token, owner) = deploy();
+    let buyer = env.next_user();
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+    token.purchase_mint(
+        buyer,
+        buyer,
+        vec![TokenId::zero()],
+        U512::from(5_000_000_000u64),
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_withdraw_by_non_admin() {
+    let (env, token, owner) = deploy();
+    let buyer = env.next_user();
+    let price = U512::from(5_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+    token.purchase_mint(buyer, buyer, vec![TokenId::zero()], price);
+    token.withdraw(buyer, price);
+}
+
+#[test]
+fn test_purchase_mint_prices_listed_tiers() {
+    let (env, token, owner) = deploy();
+    let buyer = env.next_user();
+    let price = U512::from(5_000_000_000u64);
+    let legendary_price = U512::from(20_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+    token.set_tier_price(owner, "legendary", Some(legendary_price));
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+    token.list_sale_items(
+        owner,
+        vec![TokenId::one()],
+        vec![meta::gold_dragon()],
+        Some("legendary".to_string()),
+    );
+    assert_eq!(
+        token.sale_item(TokenId::one()),
+        Some((Some("legendary".to_string()), meta::gold_dragon()))
+    );
+
+    token.purchase_mint(
+        buyer,
+        buyer,
+        vec![TokenId::zero(), TokenId::one()],
+        price + legendary_price,
+    );
+    assert_eq!(
+        token.token_meta(TokenId::zero()).unwrap(),
+        meta::red_dragon()
+    );
+    assert_eq!(
+        token.token_meta(TokenId::one()).unwrap(),
+        meta::gold_dragon()
+    );
+    assert_eq!(token.sale_item(TokenId::one()), None);
+}
+
+#[test]
+#[should_panic]
+fn test_purchase_mint_legendary_at_public_price() {
+    let (env, token, owner) = deploy();
+    let buyer = env.next_user();
+    let price = U512::from(5_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+    token.set_tier_price(owner, "legendary", Some(U512::from(20_000_000_000u64)));
+    token.list_sale_items(
+        owner,
+        vec![TokenId::zero()],
+        vec![meta::gold_dragon()],
+        Some("legendary".to_string()),
+    );
+    token.purchase_mint(buyer, buyer, vec![TokenId::zero()], price);
+}
+
+#[test]
+#[should_panic]
+fn test_purchase_mint_unlisted_token() {
+    let (env, token, owner) = deploy();
+    let buyer = env.next_user();
+    let price = U512::from(5_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+    token.purchase_mint(buyer, buyer, vec![TokenId::one()], price);
+}
+
+#[test]
+#[should_panic]
+fn test_purchase_mint_unlisted_after_unlist() {
+    let (env, token, owner) = deploy();
+    let buyer = env.next_user();
+    let price = U512::from(5_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+    token.unlist_sale_items(owner, vec![TokenId::zero()]);
+    token.purchase_mint(buyer, buyer, vec![TokenId::zero()], price);
+}
+
+#[test]
+#[should_panic]
+fn test_list_sale_items_by_non_admin() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.list_sale_items(user, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+}
+
+const PRESALE: &str = "presale";
+const PRESALE_WINDOW: (u64, u64) = (1_000, 2_000);
+
+#[test]
+fn test_phase_mint_with_allowlist() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    token.set_mint_phase(owner, PRESALE, PRESALE_WINDOW, 2, U512::zero(), None);
+    token.set_allowlist(owner, PRESALE, vec![ali], true);
+
+    env.set_block_time(1_500);
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.list_sale_items(
+        owner,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::gold_dragon()],
+        None,
+    );
+    token.phase_mint(ali, PRESALE, token_ids.clone(), vec![]);
+    assert_eq!(token.owner_of(token_ids[1]).unwrap(), Key::Account(ali));
+    assert_eq!(token.phase_minted(PRESALE, ali), 2);
+}
+
+#[test]
+#[should_panic]
+fn test_phase_mint_without_allowlist() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    token.set_mint_phase(owner, PRESALE, PRESALE_WINDOW, 2, U512::zero(), None);
+
+    env.set_block_time(1_500);
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+    token.phase_mint(ali, PRESALE, vec![TokenId::zero()], vec![]);
+}
+
+#[test]
+#[should_panic]
+fn test_phase_mint_after_phase_end() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    token.set_mint_phase(owner, PRESALE, PRESALE_WINDOW, 2, U512::zero(), None);
+    token.set_allowlist(owner, PRESALE, vec![ali], true);
+
+    env.set_block_time(PRESALE_WINDOW.1);
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+    token.phase_mint(ali, PRESALE, vec![TokenId::zero()], vec![]);
+}
+
+#[test]
+#[should_panic]
+fn test_phase_mint_over_wallet_limit() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    token.set_mint_phase(owner, PRESALE, PRESALE_WINDOW, 2, U512::zero(), None);
+    token.set_allowlist(owner, PRESALE, vec![ali], true);
+
+    env.set_block_time(1_500);
+    token.list_sale_items(
+        owner,
+        vec![TokenId::zero(), TokenId::one()],
+        vec![meta::red_dragon(), meta::gold_dragon()],
+        None,
+    );
+    token.phase_mint(ali, PRESALE, vec![TokenId::zero(), TokenId::one()], vec![]);
+    token.list_sale_items(
+        owner,
+        vec![TokenId::from(2)],
+        vec![meta::blue_dragon()],
+        None,
+    );
+    token.phase_mint(ali, PRESALE, vec![TokenId::from(2)], vec![]);
+}
+
+#[test]
+fn test_phase_mint_with_merkle_proof() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    let ali_leaf = blake2b256(ali.as_bytes());
+    let bob_leaf = blake2b256(bob.as_bytes());
+    let merkle_root = merkle_parent(ali_leaf, bob_leaf);
+    token.set_mint_phase(
+        owner,
+        PRESALE,
+        PRESALE_WINDOW,
+        0,
+        U512::zero(),
+        Some(merkle_root),
+    );
+
+    env.set_block_time(1_500);
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+    token.phase_mint(bob, PRESALE, vec![TokenId::zero()], vec![ali_leaf]);
+    assert_eq!(token.owner_of(TokenId::zero()).unwrap(), Key::Account(bob));
+}
+
+#[test]
+fn test_paid_phase_mint() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let price = U512::from(3_000_000_000u64);
+    token.set_mint_phase(owner, PRESALE, PRESALE_WINDOW, 0, price, None);
+    token.set_allowlist(owner, PRESALE, vec![ali], true);
+
+    env.set_block_time(1_500);
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+    token.phase_mint_paid(ali, PRESALE, vec![TokenId::zero()], vec![], price);
+    assert_eq!(token.owner_of(TokenId::zero()).unwrap(), Key::Account(ali));
+}
+
+#[test]
+fn test_phase_mint_ignores_forged_meta() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    token.set_mint_phase(owner, PRESALE, PRESALE_WINDOW, 2, U512::zero(), None);
+    token.set_allowlist(owner, PRESALE, vec![ali], true);
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+
+    env.set_block_time(1_500);
+    token.phase_mint_with_metas(
+        ali,
+        PRESALE,
+        vec![TokenId::zero()],
+        vec![meta::gold_dragon()],
+        vec![],
+    );
+    assert_eq!(
+        token.token_meta(TokenId::zero()).unwrap(),
+        meta::red_dragon()
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_phase_mint_unlisted_token() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    token.set_mint_phase(owner, PRESALE, PRESALE_WINDOW, 2, U512::zero(), None);
+    token.set_allowlist(owner, PRESALE, vec![ali], true);
+    token.list_sale_items(owner, vec![TokenId::zero()], vec![meta::red_dragon()], None);
+
+    env.set_block_time(1_500);
+    token.phase_mint(ali, PRESALE, vec![TokenId::from(7)], vec![]);
+}
+
+#[test]
+fn test_max_mint_per_wallet() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    let price = U512::from(1_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+    token.set_max_mint_per_wallet(owner, Some(2));
+
+    token.list_sale_items(
+        owner,
+        vec![TokenId::zero(), TokenId::one()],
+        vec![meta::red_dragon(), meta::gold_dragon()],
+        None,
+    );
+    token.purchase_mint(
+        ali,
+        ali,
+        vec![TokenId::zero(), TokenId::one()],
+        price * U512::from(2),
+    );
+    token.transfer(ali, bob, vec![TokenId::zero()]);
+    assert_eq!(token.balance_of(Key::Account(ali)), U256::one());
+    assert_eq!(token.wallet_minted(ali), 2);
+
+    // Admin mints are not counted against the cap.
+    token.mint_one(owner, ali, TokenId::from(2), meta::blue_dragon());
+    assert_eq!(token.wallet_minted(ali), 2);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(17)")]
+fn test_mint_over_max_mint_per_wallet() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let price = U512::from(1_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+    token.set_max_mint_per_wallet(owner, Some(2));
+
+    token.list_sale_items(
+        owner,
+        vec![TokenId::zero(), TokenId::one()],
+        vec![meta::red_dragon(), meta::gold_dragon()],
+        None,
+    );
+    token.purchase_mint(
+        ali,
+        ali,
+        vec![TokenId::zero(), TokenId::one()],
+        price * U512::from(2),
+    );
+    token.transfer(ali, owner, vec![TokenId::zero()]);
+    token.list_sale_items(
+        owner,
+        vec![TokenId::from(2)],
+        vec![meta::blue_dragon()],
+        None,
+    );
+    token.purchase_mint(ali, ali, vec![TokenId::from(2)], price);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(17)")]
+fn test_purchase_mint_to_capped_recipient() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    let price = U512::from(1_000_000_000u64);
+    token.set_mint_price(owner, Some(price));
+    token.set_max_mint_per_wallet(owner, Some(2));
+
+    token.list_sale_items(
+        owner,
+        vec![TokenId::zero(), TokenId::one(), TokenId::from(2)],
+        vec![meta::red_dragon(), meta::gold_dragon(), meta::blue_dragon()],
+        None,
+    );
+    token.purchase_mint(
+        ali,
+        bob,
+        vec![TokenId::zero(), TokenId::one()],
+        price * U512::from(2),
+    );
+    assert_eq!(token.wallet_minted(bob), 2);
+    token.purchase_mint(bob, bob, vec![TokenId::from(2)], price);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(17)")]
+fn test_phase_mint_over_max_mint_per_wallet() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    token.set_mint_phase(owner, PRESALE, PRESALE_WINDOW, 5, U512::zero(), None);
+    token.set_allowlist(owner, PRESALE, vec![ali], true);
+    token.set_max_mint_per_wallet(owner, Some(1));
+
+    env.set_block_time(1_500);
+    token.list_sale_items(
+        owner,
+        vec![TokenId::zero(), TokenId::one()],
+        vec![meta::red_dragon(), meta::gold_dragon()],
+        None,
+    );
+    token.phase_mint(ali, PRESALE, vec![TokenId::zero(), TokenId::one()], vec![]);
 }
@@ -3,3 +3,39 @@ pub mod cep47_tests;
 
 #[cfg(test)]
 pub mod cep47_instance;
+
+#[cfg(test)]
+pub mod cep47_string_tests;
+
+#[cfg(test)]
+pub mod cep47_string_instance;
+
+#[cfg(test)]
+pub mod multi_token_tests;
+
+#[cfg(test)]
+pub mod multi_token_instance;
+
+#[cfg(test)]
+pub mod marketplace_tests;
+
+#[cfg(test)]
+pub mod marketplace_instance;
+
+#[cfg(test)]
+pub mod auction_tests;
+
+#[cfg(test)]
+pub mod auction_instance;
+
+#[cfg(test)]
+pub mod staking_tests;
+
+#[cfg(test)]
+pub mod staking_instance;
+
+#[cfg(test)]
+pub mod vault_tests;
+
+#[cfg(test)]
+pub mod vault_instance;
@@ -0,0 +1,84 @@
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+use test_env::{TestContract, TestEnv};
+
+use crate::cep47_instance::{key_and_value_to_str, TokenId};
+
+pub struct MarketplaceInstance(TestContract, TestEnv);
+
+impl MarketplaceInstance {
+    pub fn new(env: &TestEnv, contract_name: &str, sender: AccountHash) -> MarketplaceInstance {
+        MarketplaceInstance(
+            TestContract::new(
+                env,
+                "marketplace.wasm",
+                contract_name,
+                sender,
+                runtime_args! {},
+            ),
+            env.clone(),
+        )
+    }
+
+    pub fn package_key(&self) -> Key {
+        Key::Hash(self.0.package_hash())
+    }
+
+    pub fn list(&self, sender: AccountHash, nft_contract: Key, token_id: TokenId, price: U512) {
+        self.0.call_contract(
+            sender,
+            "list",
+            runtime_args! {
+                "nft_contract" => nft_contract,
+                "token_id" => token_id,
+                "price" => price
+            },
+        )
+    }
+
+    pub fn cancel(&self, sender: AccountHash, nft_contract: Key, token_id: TokenId) {
+        self.0.call_contract(
+            sender,
+            "cancel",
+            runtime_args! {
+                "nft_contract" => nft_contract,
+                "token_id" => token_id
+            },
+        )
+    }
+
+    pub fn update_price(
+        &self,
+        sender: AccountHash,
+        nft_contract: Key,
+        token_id: TokenId,
+        price: U512,
+    ) {
+        self.0.call_contract(
+            sender,
+            "update_price",
+            runtime_args! {
+                "nft_contract" => nft_contract,
+                "token_id" => token_id,
+                "price" => price
+            },
+        )
+    }
+
+    pub fn buy(&self, sender: AccountHash, nft_contract: Key, token_id: TokenId, amount: U512) {
+        self.1.run_session(
+            sender,
+            "marketplace-buy-session.wasm",
+            runtime_args! {
+                "marketplace_contract_hash" => Key::Hash(self.0.contract_hash()),
+                "nft_contract" => nft_contract,
+                "token_id" => token_id,
+                "amount" => amount
+            },
+        )
+    }
+
+    pub fn listing(&self, nft_contract: Key, token_id: TokenId) -> Option<(Key, U512)> {
+        self.0
+            .query_dictionary("listings", key_and_value_to_str(&nft_contract, &token_id))
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+
+use casper_types::{account::AccountHash, Key, U512};
+use test_env::TestEnv;
+
+use crate::{
+    cep47_instance::{CEP47Instance, Meta, TokenId},
+    marketplace_instance::MarketplaceInstance,
+};
+
+const NAME: &str = "DragonsNFT";
+const SYMBOL: &str = "DGNFT";
+const MARKETPLACE: &str = "DragonsMarket";
+
+mod meta {
+    use super::{AccountHash, BTreeMap, Meta};
+    pub fn contract_meta(royalty_account: AccountHash) -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("origin".to_string(), "fire".to_string());
+        meta.insert(
+            "royalty_account".to_string(),
+            royalty_account.to_formatted_string(),
+        );
+        meta.insert("royalty_bps".to_string(), "500".to_string());
+        meta
+    }
+
+    pub fn red_dragon() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("color".to_string(), "red".to_string());
+        meta
+    }
+}
+
+struct Setup {
+    env: TestEnv,
+    token: CEP47Instance,
+    market: MarketplaceInstance,
+    seller: AccountHash,
+    royalty_account: AccountHash,
+}
+
+fn deploy() -> Setup {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let seller = env.next_user();
+    let royalty_account = env.next_user();
+    let token = CEP47Instance::new(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(royalty_account),
+    );
+    let market = MarketplaceInstance::new(&env, MARKETPLACE, owner);
+    token.mint_one(owner, seller, TokenId::zero(), meta::red_dragon());
+    Setup {
+        env,
+        token,
+        market,
+        seller,
+        royalty_account,
+    }
+}
+
+fn list(setup: &Setup, price: U512) {
+    setup.token.approve(
+        setup.seller,
+        setup.market.package_key(),
+        vec![TokenId::zero()],
+    );
+    setup.market.list(
+        setup.seller,
+        setup.token.contract_key(),
+        TokenId::zero(),
+        price,
+    );
+}
+
+#[test]
+fn test_list_and_buy() {
+    let setup = deploy();
+    let buyer = setup.env.next_user();
+    let nft = setup.token.contract_key();
+    let price = U512::from(100_000_000_000u64);
+    list(&setup, price);
+    assert_eq!(
+        setup.market.listing(nft, TokenId::zero()),
+        Some((Key::Account(setup.seller), price))
+    );
+
+    let seller_balance = setup.env.account_balance(setup.seller);
+    let royalty_balance = setup.env.account_balance(setup.royalty_account);
+    setup.market.buy(buyer, nft, TokenId::zero(), price);
+
+    let royalty = U512::from(5_000_000_000u64);
+    assert_eq!(
+        setup.token.owner_of(TokenId::zero()).unwrap(),
+        Key::Account(buyer)
+    );
+    assert_eq!(setup.market.listing(nft, TokenId::zero()), None);
+    assert_eq!(
+        setup.env.account_balance(setup.royalty_account),
+        royalty_balance + royalty
+    );
+    assert_eq!(
+        setup.env.account_balance(setup.seller),
+        seller_balance + price - royalty
+    );
+}
+
+#[test]
+fn test_cancel() {
+    let setup = deploy();
+    let nft = setup.token.contract_key();
+    list(&setup, U512::from(100));
+    setup.market.cancel(setup.seller, nft, TokenId::zero());
+    assert_eq!(setup.market.listing(nft, TokenId::zero()), None);
+}
+
+#[test]
+#[should_panic]
+fn test_cancel_by_non_seller() {
+    let setup = deploy();
+    let user = setup.env.next_user();
+    let nft = setup.token.contract_key();
+    list(&setup, U512::from(100));
+    setup.market.cancel(user, nft, TokenId::zero());
+}
+
+#[test]
+fn test_update_price() {
+    let setup = deploy();
+    let nft = setup.token.contract_key();
+    list(&setup, U512::from(100));
+    setup
+        .market
+        .update_price(setup.seller, nft, TokenId::zero(), U512::from(200));
+    assert_eq!(
+        setup.market.listing(nft, TokenId::zero()),
+        Some((Key::Account(setup.seller), U512::from(200)))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_buy_with_wrong_amount() {
+    let setup = deploy();
+    let buyer = setup.env.next_user();
+    let nft = setup.token.contract_key();
+    list(&setup, U512::from(100_000_000_000u64));
+    setup
+        .market
+        .buy(buyer, nft, TokenId::zero(), U512::from(99_000_000_000u64));
+}
+
+#[test]
+#[should_panic]
+fn test_list_without_approval() {
+    let setup = deploy();
+    setup.market.list(
+        setup.seller,
+        setup.token.contract_key(),
+        TokenId::zero(),
+        U512::from(100),
+    );
+}
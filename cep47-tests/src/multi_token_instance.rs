@@ -0,0 +1,148 @@
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U256};
+use test_env::{TestContract, TestEnv};
+
+use crate::cep47_instance::{key_and_value_to_str, token_key, Meta, TokenId};
+
+pub struct MultiTokenInstance(TestContract);
+
+impl MultiTokenInstance {
+    pub fn new(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        name: &str,
+        symbol: &str,
+        meta: Meta,
+    ) -> MultiTokenInstance {
+        MultiTokenInstance(TestContract::new(
+            env,
+            "multi-token.wasm",
+            contract_name,
+            sender,
+            runtime_args! {
+                "name" => name,
+                "symbol" => symbol,
+                "meta" => meta
+            },
+        ))
+    }
+
+    pub fn mint<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+        token_metas: Vec<Meta>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "mint",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "token_ids" => token_ids,
+                "amounts" => amounts,
+                "token_metas" => token_metas
+            },
+        )
+    }
+
+    pub fn burn<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        owner: T,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "burn",
+            runtime_args! {
+                "owner" => owner.into(),
+                "token_ids" => token_ids,
+                "amounts" => amounts
+            },
+        )
+    }
+
+    pub fn transfer<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "transfer",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "token_ids" => token_ids,
+                "amounts" => amounts
+            },
+        )
+    }
+
+    pub fn transfer_from<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        owner: T,
+        recipient: T,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "transfer_from",
+            runtime_args! {
+                "sender" => owner.into(),
+                "recipient" => recipient.into(),
+                "token_ids" => token_ids,
+                "amounts" => amounts
+            },
+        )
+    }
+
+    pub fn set_approval_for_all<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        operator: T,
+        approved: bool,
+    ) {
+        self.0.call_contract(
+            sender,
+            "set_approval_for_all",
+            runtime_args! {
+                "operator" => operator.into(),
+                "approved" => approved
+            },
+        )
+    }
+
+    pub fn balance_of<T: Into<Key>>(&self, owner: T, token_id: TokenId) -> U256 {
+        self.0
+            .query_dictionary("balances", key_and_value_to_str(&owner.into(), &token_id))
+            .unwrap_or_default()
+    }
+
+    pub fn is_approved_for_all<T: Into<Key>>(&self, owner: T, operator: T) -> bool {
+        let operator: Key = operator.into();
+        self.0
+            .query_dictionary("operators", key_and_value_to_str(&owner.into(), &operator))
+            .unwrap_or_default()
+    }
+
+    pub fn total_supply(&self, token_id: TokenId) -> U256 {
+        self.0
+            .query_dictionary("supplies", token_key(token_id))
+            .unwrap_or_default()
+    }
+
+    pub fn token_meta(&self, token_id: TokenId) -> Option<Meta> {
+        self.0.query_dictionary("metadata", token_key(token_id))
+    }
+
+    pub fn name(&self) -> String {
+        self.0.query_named_key(String::from("name"))
+    }
+}
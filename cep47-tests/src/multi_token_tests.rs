@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+
+use casper_types::{account::AccountHash, U256};
+use test_env::TestEnv;
+
+use crate::{
+    cep47_instance::{Meta, TokenId},
+    multi_token_instance::MultiTokenInstance,
+};
+
+const NAME: &str = "DragonItems";
+const SYMBOL: &str = "DGITM";
+
+mod meta {
+    use super::{BTreeMap, Meta};
+    pub fn contract_meta() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("origin".to_string(), "fire".to_string());
+        meta
+    }
+
+    pub fn potion() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("item".to_string(), "potion".to_string());
+        meta
+    }
+
+    pub fn ticket() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("item".to_string(), "ticket".to_string());
+        meta
+    }
+}
+
+fn deploy() -> (TestEnv, MultiTokenInstance, AccountHash) {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let token = MultiTokenInstance::new(&env, NAME, owner, NAME, SYMBOL, meta::contract_meta());
+    (env, token, owner)
+}
+
+#[test]
+fn test_deploy() {
+    let (_, token, _) = deploy();
+    assert_eq!(token.name(), NAME);
+    assert_eq!(token.total_supply(TokenId::zero()), U256::zero());
+}
+
+#[test]
+fn test_mint_batch() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+
+    token.mint(
+        owner,
+        user,
+        token_ids.clone(),
+        vec![U256::from(1000), U256::from(5)],
+        vec![meta::potion(), meta::ticket()],
+    );
+    token.mint(
+        owner,
+        user,
+        vec![token_ids[0]],
+        vec![U256::from(500)],
+        vec![meta::ticket()],
+    );
+
+    assert_eq!(token.balance_of(user, token_ids[0]), U256::from(1500));
+    assert_eq!(token.balance_of(user, token_ids[1]), U256::from(5));
+    assert_eq!(token.total_supply(token_ids[0]), U256::from(1500));
+    assert_eq!(token.token_meta(token_ids[0]).unwrap(), meta::potion());
+}
+
+#[test]
+fn test_mint_max_token_id() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint(
+        owner,
+        user,
+        vec![TokenId::MAX],
+        vec![U256::from(10)],
+        vec![meta::potion()],
+    );
+    assert_eq!(token.total_supply(TokenId::MAX), U256::from(10));
+    assert_eq!(token.token_meta(TokenId::MAX).unwrap(), meta::potion());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(7)")]
+fn test_mint_zero_amount() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint(
+        owner,
+        user,
+        vec![TokenId::zero()],
+        vec![U256::zero()],
+        vec![meta::potion()],
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(6)")]
+fn test_mint_past_max_supply() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint(
+        owner,
+        user,
+        vec![TokenId::zero()],
+        vec![U256::MAX],
+        vec![meta::potion()],
+    );
+    token.mint(
+        owner,
+        user,
+        vec![TokenId::zero()],
+        vec![U256::one()],
+        vec![meta::potion()],
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_mint_by_non_admin() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.mint(
+        user,
+        user,
+        vec![TokenId::zero()],
+        vec![U256::one()],
+        vec![meta::potion()],
+    );
+}
+
+#[test]
+fn test_transfer_batch() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.mint(
+        owner,
+        ali,
+        token_ids.clone(),
+        vec![U256::from(10), U256::from(3)],
+        vec![meta::potion(), meta::ticket()],
+    );
+
+    token.transfer(
+        ali,
+        bob,
+        token_ids.clone(),
+        vec![U256::from(4), U256::one()],
+    );
+    assert_eq!(token.balance_of(ali, token_ids[0]), U256::from(6));
+    assert_eq!(token.balance_of(ali, token_ids[1]), U256::from(2));
+    assert_eq!(token.balance_of(bob, token_ids[0]), U256::from(4));
+    assert_eq!(token.balance_of(bob, token_ids[1]), U256::one());
+    assert_eq!(token.total_supply(token_ids[0]), U256::from(10));
+}
+
+#[test]
+#[should_panic]
+fn test_transfer_more_than_balance() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    token.mint(
+        owner,
+        ali,
+        vec![TokenId::zero()],
+        vec![U256::from(2)],
+        vec![meta::potion()],
+    );
+    token.transfer(ali, bob, vec![TokenId::zero()], vec![U256::from(3)]);
+}
+
+#[test]
+fn test_transfer_from_with_operator() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    token.mint(
+        owner,
+        ali,
+        vec![TokenId::zero()],
+        vec![U256::from(10)],
+        vec![meta::potion()],
+    );
+
+    token.set_approval_for_all(ali, bob, true);
+    assert!(token.is_approved_for_all(ali, bob));
+    token.transfer_from(bob, ali, bob, vec![TokenId::zero()], vec![U256::from(7)]);
+    assert_eq!(token.balance_of(ali, TokenId::zero()), U256::from(3));
+    assert_eq!(token.balance_of(bob, TokenId::zero()), U256::from(7));
+
+    token.set_approval_for_all(ali, bob, false);
+    assert!(!token.is_approved_for_all(ali, bob));
+}
+
+#[test]
+#[should_panic]
+fn test_transfer_from_without_operator() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    token.mint(
+        owner,
+        ali,
+        vec![TokenId::zero()],
+        vec![U256::from(10)],
+        vec![meta::potion()],
+    );
+    token.transfer_from(bob, ali, bob, vec![TokenId::zero()], vec![U256::one()]);
+}
+
+#[test]
+fn test_burn() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    token.mint(
+        owner,
+        ali,
+        vec![TokenId::zero()],
+        vec![U256::from(10)],
+        vec![meta::potion()],
+    );
+    token.burn(ali, ali, vec![TokenId::zero()], vec![U256::from(4)]);
+    assert_eq!(token.balance_of(ali, TokenId::zero()), U256::from(6));
+    assert_eq!(token.total_supply(TokenId::zero()), U256::from(6));
+}
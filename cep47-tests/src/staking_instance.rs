@@ -0,0 +1,135 @@
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U256};
+use test_env::{TestContract, TestEnv};
+
+use crate::cep47_instance::{key_and_value_to_str, key_to_str, token_key, TokenId};
+
+pub struct StakingInstance(TestContract);
+
+impl StakingInstance {
+    pub fn new(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        nft_contract: Key,
+        reward_rate: U256,
+    ) -> StakingInstance {
+        StakingInstance(TestContract::new(
+            env,
+            "staking.wasm",
+            contract_name,
+            sender,
+            runtime_args! {
+                "nft_contract" => nft_contract,
+                "reward_rate" => reward_rate,
+                "name" => "Dragon Scales",
+                "symbol" => "SCALE",
+                "decimals" => 0u8
+            },
+        ))
+    }
+
+    pub fn package_key(&self) -> Key {
+        Key::Hash(self.0.package_hash())
+    }
+
+    pub fn stake(&self, sender: AccountHash, token_ids: Vec<TokenId>) {
+        self.0.call_contract(
+            sender,
+            "stake",
+            runtime_args! {
+                "token_ids" => token_ids
+            },
+        )
+    }
+
+    pub fn claim(&self, sender: AccountHash, token_ids: Vec<TokenId>) {
+        self.0.call_contract(
+            sender,
+            "claim",
+            runtime_args! {
+                "token_ids" => token_ids
+            },
+        )
+    }
+
+    pub fn unstake(&self, sender: AccountHash, token_ids: Vec<TokenId>) {
+        self.0.call_contract(
+            sender,
+            "unstake",
+            runtime_args! {
+                "token_ids" => token_ids
+            },
+        )
+    }
+
+    pub fn transfer<T: Into<Key>>(&self, sender: AccountHash, recipient: T, amount: U256) {
+        self.0.call_contract(
+            sender,
+            "transfer",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "amount" => amount
+            },
+        )
+    }
+
+    pub fn approve<T: Into<Key>>(&self, sender: AccountHash, spender: T, amount: U256) {
+        self.0.call_contract(
+            sender,
+            "approve",
+            runtime_args! {
+                "spender" => spender.into(),
+                "amount" => amount
+            },
+        )
+    }
+
+    pub fn transfer_from<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        owner: T,
+        recipient: T,
+        amount: U256,
+    ) {
+        self.0.call_contract(
+            sender,
+            "transfer_from",
+            runtime_args! {
+                "owner" => owner.into(),
+                "recipient" => recipient.into(),
+                "amount" => amount
+            },
+        )
+    }
+
+    pub fn allowance<T: Into<Key>>(&self, owner: T, spender: T) -> U256 {
+        self.0
+            .query_dictionary(
+                "allowances",
+                key_and_value_to_str(&owner.into(), &spender.into()),
+            )
+            .unwrap_or_default()
+    }
+
+    pub fn staker_of(&self, token_id: TokenId) -> Option<Key> {
+        self.0
+            .query_dictionary::<(Key, u64)>("stakes", token_key(token_id))
+            .map(|(staker, _)| staker)
+    }
+
+    pub fn staked_tokens<T: Into<Key>>(&self, staker: T) -> Vec<TokenId> {
+        self.0
+            .query_dictionary("staked_tokens", key_to_str(&staker.into()))
+            .unwrap_or_default()
+    }
+
+    pub fn balance_of<T: Into<Key>>(&self, owner: T) -> U256 {
+        self.0
+            .query_dictionary("balances", key_to_str(&owner.into()))
+            .unwrap_or_default()
+    }
+
+    pub fn total_supply(&self) -> U256 {
+        self.0.query_named_key(String::from("total_supply"))
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+
+use casper_types::{account::AccountHash, AccessRights, Key, URef, U256};
+use test_env::TestEnv;
+
+use crate::{
+    cep47_instance::{CEP47Instance, Meta, TokenId},
+    staking_instance::StakingInstance,
+};
+
+const NAME: &str = "DragonsNFT";
+const SYMBOL: &str = "DGNFT";
+const STAKING: &str = "DragonsStaking";
+const REWARD_RATE: u64 = 5;
+
+mod meta {
+    use super::{BTreeMap, Meta};
+    pub fn contract_meta() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("origin".to_string(), "fire".to_string());
+        meta
+    }
+
+    pub fn red_dragon() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("color".to_string(), "red".to_string());
+        meta
+    }
+
+    pub fn blue_dragon() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("color".to_string(), "blue".to_string());
+        meta
+    }
+}
+
+struct Setup {
+    env: TestEnv,
+    token: CEP47Instance,
+    staking: StakingInstance,
+    staker: AccountHash,
+}
+
+fn deploy() -> Setup {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let staker = env.next_user();
+    let token = CEP47Instance::new(&env, NAME, owner, NAME, SYMBOL, meta::contract_meta());
+    let staking = StakingInstance::new(
+        &env,
+        STAKING,
+        owner,
+        token.contract_key(),
+        U256::from(REWARD_RATE),
+    );
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.mint_many(
+        owner,
+        staker,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    token.approve(staker, staking.package_key(), token_ids);
+    Setup {
+        env,
+        token,
+        staking,
+        staker,
+    }
+}
+
+#[test]
+fn test_stake() {
+    let setup = deploy();
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    setup.env.set_block_time(1_000);
+    setup.staking.stake(setup.staker, token_ids.clone());
+    assert_eq!(
+        setup.token.owner_of(TokenId::zero()).unwrap(),
+        setup.staking.package_key()
+    );
+    assert_eq!(
+        setup.staking.staker_of(TokenId::one()).unwrap(),
+        Key::Account(setup.staker)
+    );
+    assert_eq!(setup.staking.staked_tokens(setup.staker), token_ids);
+}
+
+#[test]
+fn test_claim() {
+    let setup = deploy();
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    setup.env.set_block_time(1_000);
+    setup.staking.stake(setup.staker, token_ids.clone());
+
+    setup.env.set_block_time(11_000);
+    setup.staking.claim(setup.staker, token_ids.clone());
+    assert_eq!(setup.staking.balance_of(setup.staker), U256::from(100));
+
+    // Accrual restarts from the claim.
+    setup.env.set_block_time(13_000);
+    setup.staking.claim(setup.staker, token_ids);
+    assert_eq!(setup.staking.balance_of(setup.staker), U256::from(120));
+    assert_eq!(setup.staking.total_supply(), U256::from(120));
+}
+
+#[test]
+fn test_claim_carries_fractional_rewards() {
+    let setup = deploy();
+    setup.env.set_block_time(1_000);
+    setup.staking.stake(setup.staker, vec![TokenId::zero()]);
+
+    // Half a unit is not paid out, but is not lost either.
+    setup.env.set_block_time(1_100);
+    setup.staking.claim(setup.staker, vec![TokenId::zero()]);
+    assert_eq!(setup.staking.balance_of(setup.staker), U256::zero());
+
+    setup.env.set_block_time(1_200);
+    setup.staking.claim(setup.staker, vec![TokenId::zero()]);
+    assert_eq!(setup.staking.balance_of(setup.staker), U256::one());
+}
+
+#[test]
+fn test_unstake() {
+    let setup = deploy();
+    setup.env.set_block_time(1_000);
+    setup
+        .staking
+        .stake(setup.staker, vec![TokenId::zero(), TokenId::one()]);
+
+    setup.env.set_block_time(21_000);
+    setup.staking.unstake(setup.staker, vec![TokenId::zero()]);
+    assert_eq!(
+        setup.token.owner_of(TokenId::zero()).unwrap(),
+        Key::Account(setup.staker)
+    );
+    assert_eq!(setup.staking.staker_of(TokenId::zero()), None);
+    assert_eq!(
+        setup.staking.staked_tokens(setup.staker),
+        vec![TokenId::one()]
+    );
+    assert_eq!(setup.staking.balance_of(setup.staker), U256::from(100));
+}
+
+#[test]
+#[should_panic]
+fn test_stake_without_approval() {
+    let setup = deploy();
+    let user = setup.env.next_user();
+    setup.staking.stake(user, vec![TokenId::zero()]);
+}
+
+#[test]
+#[should_panic]
+fn test_claim_by_non_staker() {
+    let setup = deploy();
+    let user = setup.env.next_user();
+    setup.env.set_block_time(1_000);
+    setup.staking.stake(setup.staker, vec![TokenId::zero()]);
+    setup.staking.claim(user, vec![TokenId::zero()]);
+}
+
+#[test]
+#[should_panic]
+fn test_unstake_by_non_staker() {
+    let setup = deploy();
+    let user = setup.env.next_user();
+    setup.env.set_block_time(1_000);
+    setup.staking.stake(setup.staker, vec![TokenId::zero()]);
+    setup.staking.unstake(user, vec![TokenId::zero()]);
+}
+
+#[test]
+fn test_transfer_rewards() {
+    let setup = deploy();
+    let user = setup.env.next_user();
+    setup.env.set_block_time(1_000);
+    setup.staking.stake(setup.staker, vec![TokenId::zero()]);
+    setup.env.set_block_time(11_000);
+    setup.staking.claim(setup.staker, vec![TokenId::zero()]);
+
+    setup.staking.transfer(setup.staker, user, U256::from(20));
+    assert_eq!(setup.staking.balance_of(setup.staker), U256::from(30));
+    assert_eq!(setup.staking.balance_of(user), U256::from(20));
+}
+
+#[test]
+fn test_transfer_rewards_to_account_hash_and_uref() {
+    let setup = deploy();
+    let account = Key::Account(AccountHash::new([7u8; 32]));
+    let hash = Key::Hash([7u8; 32]);
+    let purse = Key::URef(URef::new([7u8; 32], AccessRights::READ_ADD_WRITE));
+    setup.env.set_block_time(1_000);
+    setup.staking.stake(setup.staker, vec![TokenId::zero()]);
+    setup.env.set_block_time(11_000);
+    setup.staking.claim(setup.staker, vec![TokenId::zero()]);
+
+    setup
+        .staking
+        .transfer(setup.staker, account, U256::from(10));
+    setup.staking.transfer(setup.staker, hash, U256::from(15));
+    setup.staking.transfer(setup.staker, purse, U256::from(20));
+    assert_eq!(setup.staking.balance_of(account), U256::from(10));
+    assert_eq!(setup.staking.balance_of(hash), U256::from(15));
+    assert_eq!(setup.staking.balance_of(purse), U256::from(20));
+    assert_eq!(setup.staking.balance_of(setup.staker), U256::from(5));
+}
+
+#[test]
+fn test_approve_and_transfer_from_rewards() {
+    let setup = deploy();
+    let spender = setup.env.next_user();
+    let user = setup.env.next_user();
+    setup.env.set_block_time(1_000);
+    setup.staking.stake(setup.staker, vec![TokenId::zero()]);
+    setup.env.set_block_time(11_000);
+    setup.staking.claim(setup.staker, vec![TokenId::zero()]);
+
+    setup.staking.approve(setup.staker, spender, U256::from(30));
+    assert_eq!(
+        setup.staking.allowance(setup.staker, spender),
+        U256::from(30)
+    );
+    setup
+        .staking
+        .transfer_from(spender, setup.staker, user, U256::from(20));
+    assert_eq!(setup.staking.balance_of(setup.staker), U256::from(30));
+    assert_eq!(setup.staking.balance_of(user), U256::from(20));
+    assert_eq!(
+        setup.staking.allowance(setup.staker, spender),
+        U256::from(10)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_transfer_from_over_allowance() {
+    let setup = deploy();
+    let spender = setup.env.next_user();
+    setup.env.set_block_time(1_000);
+    setup.staking.stake(setup.staker, vec![TokenId::zero()]);
+    setup.env.set_block_time(11_000);
+    setup.staking.claim(setup.staker, vec![TokenId::zero()]);
+
+    setup.staking.approve(setup.staker, spender, U256::from(10));
+    setup
+        .staking
+        .transfer_from(spender, setup.staker, spender, U256::from(20));
+}
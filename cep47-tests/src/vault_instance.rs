@@ -0,0 +1,113 @@
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U256, U512};
+use test_env::{TestContract, TestEnv};
+
+use crate::cep47_instance::{key_to_str, TokenId};
+
+pub struct VaultInstance(TestContract, TestEnv);
+
+impl VaultInstance {
+    pub fn new(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        nft_contract: Key,
+        token_id: TokenId,
+        share_supply: U256,
+        reserve_price: U512,
+    ) -> VaultInstance {
+        VaultInstance(
+            TestContract::new(
+                env,
+                "vault.wasm",
+                contract_name,
+                sender,
+                runtime_args! {
+                    "name" => "Red Dragon Shares",
+                    "symbol" => "RDS",
+                    "nft_contract" => nft_contract,
+                    "token_id" => token_id,
+                    "share_supply" => share_supply,
+                    "reserve_price" => reserve_price
+                },
+            ),
+            env.clone(),
+        )
+    }
+
+    pub fn package_key(&self) -> Key {
+        Key::Hash(self.0.package_hash())
+    }
+
+    pub fn lock(&self, sender: AccountHash) {
+        self.0.call_contract(sender, "lock", runtime_args! {})
+    }
+
+    pub fn buyout(&self, sender: AccountHash, amount: U512) {
+        self.1.run_session(
+            sender,
+            "vault-buyout-session.wasm",
+            runtime_args! {
+                "vault_contract_hash" => Key::Hash(self.0.contract_hash()),
+                "amount" => amount
+            },
+        )
+    }
+
+    pub fn redeem(&self, sender: AccountHash) {
+        self.0.call_contract(sender, "redeem", runtime_args! {})
+    }
+
+    pub fn transfer<T: Into<Key>>(&self, sender: AccountHash, recipient: T, amount: U256) {
+        self.0.call_contract(
+            sender,
+            "transfer",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "amount" => amount
+            },
+        )
+    }
+
+    pub fn approve<T: Into<Key>>(&self, sender: AccountHash, spender: T, amount: U256) {
+        self.0.call_contract(
+            sender,
+            "approve",
+            runtime_args! {
+                "spender" => spender.into(),
+                "amount" => amount
+            },
+        )
+    }
+
+    pub fn transfer_from<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        owner: T,
+        recipient: T,
+        amount: U256,
+    ) {
+        self.0.call_contract(
+            sender,
+            "transfer_from",
+            runtime_args! {
+                "owner" => owner.into(),
+                "recipient" => recipient.into(),
+                "amount" => amount
+            },
+        )
+    }
+
+    pub fn balance_of<T: Into<Key>>(&self, owner: T) -> U256 {
+        self.0
+            .query_dictionary("balances", key_to_str(&owner.into()))
+            .unwrap_or_default()
+    }
+
+    pub fn total_supply(&self) -> U256 {
+        self.0.query_named_key(String::from("total_supply"))
+    }
+
+    pub fn state(&self) -> u8 {
+        self.0.query_named_key(String::from("state"))
+    }
+}
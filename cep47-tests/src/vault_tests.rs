@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+
+use casper_engine_test_support::DEFAULT_PAYMENT;
+use casper_types::{account::AccountHash, Key, U256, U512};
+use test_env::TestEnv;
+
+use crate::{
+    cep47_instance::{CEP47Instance, Meta, TokenId},
+    vault_instance::VaultInstance,
+};
+
+const NAME: &str = "DragonsNFT";
+const SYMBOL: &str = "DGNFT";
+const VAULT: &str = "RedDragonVault";
+const SHARES: u64 = 1_000;
+
+mod meta {
+    use super::{BTreeMap, Meta};
+    pub fn contract_meta() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("origin".to_string(), "fire".to_string());
+        meta
+    }
+
+    pub fn red_dragon() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("color".to_string(), "red".to_string());
+        meta
+    }
+}
+
+fn reserve_price() -> U512 {
+    U512::from(10_000_000_000_000u64)
+}
+
+struct Setup {
+    env: TestEnv,
+    token: CEP47Instance,
+    vault: VaultInstance,
+    curator: AccountHash,
+}
+
+fn deploy() -> Setup {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let curator = env.next_user();
+    let token = CEP47Instance::new(&env, NAME, owner, NAME, SYMBOL, meta::contract_meta());
+    token.mint_one(owner, curator, TokenId::zero(), meta::red_dragon());
+    let vault = VaultInstance::new(
+        &env,
+        VAULT,
+        curator,
+        token.contract_key(),
+        TokenId::zero(),
+        U256::from(SHARES),
+        reserve_price(),
+    );
+    token.approve(curator, vault.package_key(), vec![TokenId::zero()]);
+    vault.lock(curator);
+    Setup {
+        env,
+        token,
+        vault,
+        curator,
+    }
+}
+
+#[test]
+fn test_lock() {
+    let setup = deploy();
+    assert_eq!(
+        setup.token.owner_of(TokenId::zero()).unwrap(),
+        setup.vault.package_key()
+    );
+    assert_eq!(setup.vault.balance_of(setup.curator), U256::from(SHARES));
+    assert_eq!(setup.vault.total_supply(), U256::from(SHARES));
+    assert_eq!(setup.vault.state(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_lock_twice() {
+    let setup = deploy();
+    setup.vault.lock(setup.curator);
+}
+
+#[test]
+fn test_share_transfers() {
+    let setup = deploy();
+    let alice = setup.env.next_user();
+    let bob = setup.env.next_user();
+    setup.vault.transfer(setup.curator, alice, U256::from(300));
+    setup.vault.approve(alice, bob, U256::from(100));
+    setup.vault.transfer_from(bob, alice, bob, U256::from(100));
+    assert_eq!(setup.vault.balance_of(setup.curator), U256::from(700));
+    assert_eq!(setup.vault.balance_of(alice), U256::from(200));
+    assert_eq!(setup.vault.balance_of(bob), U256::from(100));
+}
+
+#[test]
+#[should_panic]
+fn test_share_transfer_from_without_allowance() {
+    let setup = deploy();
+    let bob = setup.env.next_user();
+    setup
+        .vault
+        .transfer_from(bob, setup.curator, bob, U256::from(100));
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(7)")]
+fn test_share_transfer_to_contract() {
+    let setup = deploy();
+    setup
+        .vault
+        .transfer(setup.curator, setup.token.contract_key(), U256::from(100));
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(7)")]
+fn test_share_transfer_from_to_contract() {
+    let setup = deploy();
+    let bob = setup.env.next_user();
+    setup.vault.approve(setup.curator, bob, U256::from(100));
+    setup.vault.transfer_from(
+        bob,
+        setup.curator,
+        setup.token.contract_key(),
+        U256::from(100),
+    );
+}
+
+#[test]
+fn test_buyout_and_redeem() {
+    let setup = deploy();
+    let alice = setup.env.next_user();
+    let buyer = setup.env.next_user();
+    setup.vault.transfer(setup.curator, alice, U256::from(250));
+
+    setup.vault.buyout(buyer, reserve_price());
+    assert_eq!(
+        setup.token.owner_of(TokenId::zero()).unwrap(),
+        Key::Account(buyer)
+    );
+    assert_eq!(setup.vault.state(), 2);
+
+    let alice_balance = setup.env.account_balance(alice);
+    setup.vault.redeem(alice);
+    assert!(
+        setup.env.account_balance(alice) + *DEFAULT_PAYMENT
+            >= alice_balance + reserve_price() / U512::from(4)
+    );
+    assert_eq!(setup.vault.balance_of(alice), U256::zero());
+    assert_eq!(setup.vault.total_supply(), U256::from(750));
+
+    setup.vault.redeem(setup.curator);
+    assert_eq!(setup.vault.total_supply(), U256::zero());
+}
+
+#[test]
+#[should_panic]
+fn test_buyout_with_wrong_amount() {
+    let setup = deploy();
+    let buyer = setup.env.next_user();
+    setup.vault.buyout(buyer, reserve_price() - U512::one());
+}
+
+#[test]
+#[should_panic]
+fn test_redeem_before_buyout() {
+    let setup = deploy();
+    setup.vault.redeem(setup.curator);
+}
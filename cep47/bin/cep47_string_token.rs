@@ -0,0 +1,8 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+// Tokens of this collection are identified by strings, such as UUIDs or
+// content hashes, instead of numbers.
+cep47::cep47_contract!(alloc::string::String);
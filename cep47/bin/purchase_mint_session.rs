@@ -0,0 +1,10 @@
+#![no_main]
+#![no_std]
+
+use cep47::TokenId;
+
+/// Pays for a `purchase_mint` on a collection identified by numbers.
+#[no_mangle]
+fn call() {
+    cep47::session::purchase_mint::<TokenId>();
+}
@@ -0,0 +1,12 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+/// Pays for a `purchase_mint` on a collection identified by strings.
+#[no_mangle]
+fn call() {
+    cep47::session::purchase_mint::<String>();
+}
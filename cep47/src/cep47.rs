@@ -1,12 +1,20 @@
 use crate::{
-    data::{self, Allowances, Metadata, OwnedTokens, Owners},
+    data::{
+        self, Allowances, BurnedTokens, Checksums, Children, FrozenMetadata, MetaCommitments,
+        Metadata, MetadataHistory, MetadataVersions, MintPhases, OwnedTokens, Owners, Parents,
+        PhaseAllowlist, PhaseMints, SaleItems, TierPrices, TokenUris, WalletMints,
+    },
     event::CEP47Event,
-    Meta, TokenId,
+    Checksum, Meta, MetaSchema, TokenId, TokenIdentifier,
 };
 use alloc::{string::String, vec::Vec};
-use casper_types::{ApiError, Key, U256};
-use contract_utils::{ContractContext, ContractStorage};
-use core::convert::TryInto;
+use casper_contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{bytesrepr::ToBytes, ApiError, Key, U256};
+use contract_utils::{legacy_key_strs, set_legacy_key_strs, ContractContext, ContractStorage};
+use core::convert::{TryFrom, TryInto};
 
 #[repr(u16)]
 pub enum Error {
@@ -14,6 +22,34 @@ pub enum Error {
     WrongArguments = 2,
     TokenIdAlreadyExists = 3,
     TokenIdDoesntExist = 4,
+    UnsupportedStorageVersion = 5,
+    TokenIsAttached = 6,
+    TokenIsNotAttached = 7,
+    TokenHasChildren = 8,
+    InvalidParent = 9,
+    SaleNotActive = 10,
+    UnknownTier = 11,
+    WrongPaymentAmount = 12,
+    UnknownPhase = 13,
+    PhaseNotActive = 14,
+    NotAllowlisted = 15,
+    PhaseLimitExceeded = 16,
+    MintLimitExceeded = 17,
+    BurnDisabled = 18,
+    TokenIdBurned = 19,
+    // 20 is reverted with by `AdminControl`.
+    MetadataFrozen = 21,
+    MetadataKeyMissing = 22,
+    MetadataKeyNotAllowed = 23,
+    MetadataEntryTooLong = 24,
+    MetadataTooLarge = 25,
+    InvalidChecksum = 26,
+    AlreadyRevealed = 27,
+    CollectionMetadataLocked = 28,
+    InvalidRecipient = 29,
+    TokenNotForSale = 30,
+    NotRevealed = 31,
+    MetaCommitmentMismatch = 32,
 }
 
 impl From<Error> for ApiError {
@@ -22,16 +58,252 @@ impl From<Error> for ApiError {
     }
 }
 
-pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
+/// Who may burn tokens, chosen when the collection is installed.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum BurnPolicy {
+    /// Only the token owner.
+    OwnerOnly = 0,
+    /// The token owner or the spender approved for the token.
+    OwnerOrApproved = 1,
+    /// Only collection admins, through `burn_as_admin`.
+    Admin = 2,
+    /// Tokens can't be burned.
+    Disabled = 3,
+}
+
+impl TryFrom<u8> for BurnPolicy {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(BurnPolicy::OwnerOnly),
+            1 => Ok(BurnPolicy::OwnerOrApproved),
+            2 => Ok(BurnPolicy::Admin),
+            3 => Ok(BurnPolicy::Disabled),
+            _ => Err(Error::WrongArguments),
+        }
+    }
+}
+
+/// Which kinds of keys may receive tokens, chosen when the collection is
+/// installed.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum RecipientPolicy {
+    /// Only accounts.
+    AccountsOnly = 0,
+    /// Accounts and contracts.
+    AccountsAndContracts = 1,
+}
+
+impl TryFrom<u8> for RecipientPolicy {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(RecipientPolicy::AccountsOnly),
+            1 => Ok(RecipientPolicy::AccountsAndContracts),
+            _ => Err(Error::WrongArguments),
+        }
+    }
+}
+
+pub trait CEP47<Storage: ContractStorage, Id: TokenIdentifier = TokenId>:
+    ContractContext<Storage>
+{
     fn init(&mut self, name: String, symbol: String, meta: Meta) {
         data::set_name(name);
         data::set_symbol(symbol);
         data::set_meta(meta);
         data::set_total_supply(U256::zero());
-        Owners::init();
-        OwnedTokens::init();
-        Metadata::init();
+        data::set_storage_version(data::CURRENT_STORAGE_VERSION);
+        Owners::<Id>::init();
+        OwnedTokens::<Id>::init();
+        Metadata::<Id>::init();
         Allowances::init();
+        Parents::<Id>::init();
+        Children::<Id>::init();
+        self.init_sale_storage();
+        self.init_phase_storage();
+        self.init_wallet_limit_storage();
+        self.init_burn_storage();
+        self.init_freeze_storage();
+        self.init_meta_history_storage();
+        self.init_uri_storage();
+        self.init_checksum_storage();
+        self.init_reveal_storage();
+        self.init_collection_lock_storage();
+        self.init_sale_item_storage();
+        self.init_meta_commitment_storage();
+        data::set_legacy_token_keys(false);
+        set_legacy_key_strs(false);
+    }
+
+    /// Sale proceeds go to the installer until an admin sets another treasury.
+    fn init_sale_storage(&mut self) {
+        data::set_purse(system::create_purse());
+        data::set_mint_price(None);
+        data::set_treasury(self.get_caller().into_account().unwrap_or_revert());
+        TierPrices::init();
+    }
+
+    fn init_phase_storage(&mut self) {
+        MintPhases::init();
+        PhaseAllowlist::init();
+        PhaseMints::init();
+    }
+
+    fn init_wallet_limit_storage(&mut self) {
+        data::set_max_mint_per_wallet(None);
+        WalletMints::init();
+    }
+
+    /// Burned ids may be minted again until `set_forbid_remint` says otherwise.
+    fn init_burn_storage(&mut self) {
+        data::set_burned_supply(U256::zero());
+        data::set_forbid_remint(false);
+        BurnedTokens::<Id>::init();
+    }
+
+    fn init_freeze_storage(&mut self) {
+        data::set_all_meta_frozen(false);
+        FrozenMetadata::<Id>::init();
+    }
+
+    /// History is off until `set_keep_meta_history` turns it on.
+    fn init_meta_history_storage(&mut self) {
+        data::set_keep_meta_history(false);
+        MetadataVersions::<Id>::init();
+        MetadataHistory::init();
+    }
+
+    fn init_uri_storage(&mut self) {
+        data::set_base_uri(String::new());
+        TokenUris::<Id>::init();
+    }
+
+    fn init_checksum_storage(&mut self) {
+        Checksums::<Id>::init();
+    }
+
+    /// Collections start out revealed unless installed with a placeholder.
+    fn init_reveal_storage(&mut self) {
+        data::set_provenance_hash(None);
+        data::set_placeholder_meta(None);
+        data::set_reveal_offset(None);
+        data::set_reveal_supply(U256::zero());
+    }
+
+    fn init_collection_lock_storage(&mut self) {
+        data::set_collection_meta_locked(false);
+    }
+
+    fn init_sale_item_storage(&mut self) {
+        SaleItems::<Id>::init();
+    }
+
+    fn init_meta_commitment_storage(&mut self) {
+        MetaCommitments::<Id>::init();
+    }
+
+    fn migrate(&mut self) -> Result<(), Error> {
+        let mut version = data::storage_version();
+        if version > data::CURRENT_STORAGE_VERSION {
+            return Err(Error::UnsupportedStorageVersion);
+        }
+        while version < data::CURRENT_STORAGE_VERSION {
+            self.migrate_from(version);
+            version += 1;
+            data::set_storage_version(version);
+        }
+        Ok(())
+    }
+
+    /// Brings storage written by layout `version` up to `version + 1`.
+    fn migrate_from(&mut self, version: u32) {
+        match version {
+            1 => {
+                Parents::<Id>::init();
+                Children::<Id>::init();
+            }
+            2 => self.init_sale_storage(),
+            3 => self.init_phase_storage(),
+            4 => self.init_wallet_limit_storage(),
+            5 => self.init_burn_storage(),
+            6 => self.init_freeze_storage(),
+            7 => self.init_meta_history_storage(),
+            8 => self.init_uri_storage(),
+            9 => self.init_checksum_storage(),
+            10 => self.init_reveal_storage(),
+            11 => self.init_collection_lock_storage(),
+            // Items written so far are keyed by the decimal token id.
+            12 => data::set_legacy_token_keys(true),
+            13 => self.init_sale_item_storage(),
+            // Account and hash entries are keyed by their plain hex.
+            14 => set_legacy_key_strs(true),
+            15 => self.init_meta_commitment_storage(),
+            _ => {}
+        }
+    }
+
+    fn legacy_token_keys(&self) -> bool {
+        data::legacy_token_keys()
+    }
+
+    /// Moves everything stored for `token_ids` under decimal keys to fixed
+    /// length keys. Dictionaries can't be listed on chain, so callers pass the
+    /// ids, e.g. from the mint events.
+    fn migrate_token_keys(&mut self, token_ids: Vec<Id>) {
+        if !data::legacy_token_keys() {
+            return;
+        }
+        let owners_dict = Owners::<Id>::instance();
+        let metadata_dict = Metadata::<Id>::instance();
+        let parents_dict = Parents::<Id>::instance();
+        let children_dict = Children::<Id>::instance();
+        let burned_tokens_dict = BurnedTokens::<Id>::instance();
+        let frozen_metadata_dict = FrozenMetadata::<Id>::instance();
+        let versions_dict = MetadataVersions::<Id>::instance();
+        let token_uris_dict = TokenUris::<Id>::instance();
+        let checksums_dict = Checksums::<Id>::instance();
+        let history_dict = MetadataHistory::instance();
+        for token_id in &token_ids {
+            owners_dict.migrate(token_id);
+            metadata_dict.migrate(token_id);
+            parents_dict.migrate(token_id);
+            children_dict.migrate(token_id);
+            burned_tokens_dict.migrate(token_id);
+            frozen_metadata_dict.migrate(token_id);
+            versions_dict.migrate(token_id);
+            history_dict.migrate(token_id, versions_dict.get(token_id));
+            token_uris_dict.migrate(token_id);
+            checksums_dict.migrate(token_id);
+        }
+    }
+
+    /// Stops looking up decimal keys once every token has been migrated.
+    fn finish_token_key_migration(&mut self) {
+        data::set_legacy_token_keys(false);
+    }
+
+    /// Moves the balances and mint counts of `accounts` from plain hex to
+    /// hashed item keys, see `contract_utils::key_to_str`.
+    fn migrate_account_keys(&mut self, accounts: Vec<Key>) {
+        if !legacy_key_strs() {
+            return;
+        }
+        let owned_tokens_dict = OwnedTokens::<Id>::instance();
+        let wallet_mints_dict = WalletMints::instance();
+        for account in &accounts {
+            owned_tokens_dict.migrate_balance(account);
+            wallet_mints_dict.migrate(account);
+        }
+    }
+
+    /// Stops looking up plain hex keys once every account has been migrated.
+    fn finish_account_key_migration(&mut self) {
+        set_legacy_key_strs(false);
     }
 
     fn name(&self) -> String {
@@ -46,41 +318,488 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         data::meta()
     }
 
+    fn collection_meta_locked(&self) -> bool {
+        data::collection_meta_locked()
+    }
+
+    /// Makes name, symbol and collection meta immutable. There is no way
+    /// back.
+    fn lock_collection_meta(&mut self) {
+        data::set_collection_meta_locked(true);
+    }
+
+    fn set_name(&mut self, name: String) -> Result<(), Error> {
+        self.assert_collection_meta_unlocked()?;
+        data::set_name(name);
+        self.emit_collection_update("name", Vec::new());
+        Ok(())
+    }
+
+    fn set_symbol(&mut self, symbol: String) -> Result<(), Error> {
+        self.assert_collection_meta_unlocked()?;
+        data::set_symbol(symbol);
+        self.emit_collection_update("symbol", Vec::new());
+        Ok(())
+    }
+
+    fn set_meta(&mut self, meta: Meta) -> Result<(), Error> {
+        self.assert_collection_meta_unlocked()?;
+        let changed_keys = changed_keys(&data::meta(), &meta);
+        data::set_meta(meta);
+        self.emit_collection_update("meta", changed_keys);
+        Ok(())
+    }
+
+    /// Merges `set` into the collection meta and drops the `remove` keys.
+    fn update_meta_keys(&mut self, set: Meta, remove: Vec<String>) -> Result<(), Error> {
+        if remove.iter().any(|key| set.contains_key(key)) {
+            return Err(Error::WrongArguments);
+        }
+        let mut meta = data::meta();
+        for key in &remove {
+            meta.remove(key);
+        }
+        meta.extend(set);
+        self.set_meta(meta)
+    }
+
+    fn assert_collection_meta_unlocked(&self) -> Result<(), Error> {
+        if data::collection_meta_locked() {
+            return Err(Error::CollectionMetadataLocked);
+        }
+        Ok(())
+    }
+
+    fn emit_collection_update(&mut self, field: &str, changed_keys: Vec<String>) {
+        self.emit(CEP47Event::CollectionMetadataUpdate {
+            field: field.into(),
+            changed_keys,
+        });
+    }
+
     fn total_supply(&self) -> U256 {
         data::total_supply()
     }
 
     fn balance_of(&self, owner: Key) -> U256 {
-        OwnedTokens::instance().get_balances(&owner)
+        OwnedTokens::<Id>::instance().get_balances(&owner)
+    }
+
+    fn owner_of(&self, token_id: Id) -> Option<Key> {
+        Owners::<Id>::instance().get(&token_id)
+    }
+
+    /// Until the collection, and then the token's own metadata, is revealed
+    /// the token shows the placeholder.
+    fn token_meta(&self, token_id: Id) -> Option<Meta> {
+        self.owner_of(token_id.clone())?;
+        if !self.is_revealed() {
+            return data::placeholder_meta();
+        }
+        Metadata::<Id>::instance()
+            .get(&token_id)
+            .or_else(data::placeholder_meta)
+    }
+
+    fn provenance_hash(&self) -> Option<String> {
+        data::provenance_hash()
+    }
+
+    fn is_revealed(&self) -> bool {
+        data::placeholder_meta().is_none() || data::reveal_offset().is_some()
+    }
+
+    /// Commits to the hash of the final assets and hides token metadata
+    /// behind `placeholder` until `reveal`. Tokens minted meanwhile only
+    /// store a digest of their metadata, see `reveal_token_meta`.
+    fn set_pending_reveal(&mut self, provenance_hash: String, placeholder: Meta) {
+        data::set_provenance_hash(Some(provenance_hash));
+        data::set_placeholder_meta(Some(placeholder));
+    }
+
+    /// Shows the real metadata and points token URIs at `base_uri`. The
+    /// asset index is shifted by `offset_seed` modulo the supply at reveal,
+    /// so nobody could pick tokens by their final asset while minting.
+    fn reveal(&mut self, base_uri: String, offset_seed: U256) -> Result<(), Error> {
+        if self.is_revealed() {
+            return Err(Error::AlreadyRevealed);
+        }
+        if data::all_meta_frozen() {
+            return Err(Error::MetadataFrozen);
+        }
+        let supply = data::total_supply();
+        let offset = if supply.is_zero() {
+            U256::zero()
+        } else {
+            offset_seed % supply
+        };
+        data::set_reveal_supply(supply);
+        data::set_reveal_offset(Some(offset));
+        data::set_base_uri(base_uri.clone());
+        self.emit(CEP47Event::Reveal { base_uri, offset });
+        Ok(())
+    }
+
+    /// Publishes the metadata of tokens minted before the reveal. A token
+    /// receives the metadata committed at mint by the token its asset index
+    /// points at, the same shift `token_uri` applies, so `token_metas` must
+    /// hash to that token's commitment.
+    fn reveal_token_meta(
+        &mut self,
+        token_ids: Vec<Id>,
+        token_metas: Vec<Meta>,
+    ) -> Result<(), Error> {
+        if token_ids.len() != token_metas.len() {
+            return Err(Error::WrongArguments);
+        }
+        if !self.is_revealed() {
+            return Err(Error::NotRevealed);
+        }
+        let commitments_dict = MetaCommitments::<Id>::instance();
+        let metadata_dict = Metadata::<Id>::instance();
+        for (token_id, meta) in token_ids.into_iter().zip(token_metas) {
+            if self.owner_of(token_id.clone()).is_none() {
+                return Err(Error::TokenIdDoesntExist);
+            }
+            if metadata_dict.get(&token_id).is_some() {
+                return Err(Error::AlreadyRevealed);
+            }
+            let asset_token = self.asset_token(&token_id);
+            if commitments_dict.get(&asset_token) != Some(meta_commitment(&meta)) {
+                return Err(Error::MetaCommitmentMismatch);
+            }
+            commitments_dict.remove(&asset_token);
+            metadata_dict.set(&token_id, meta.clone());
+            self.emit(CEP47Event::MetadataUpdate {
+                token_id,
+                changed_keys: meta.keys().cloned().collect(),
+                version: 0,
+            });
+        }
+        Ok(())
     }
 
-    fn owner_of(&self, token_id: TokenId) -> Option<Key> {
-        Owners::instance().get(&token_id)
+    /// Token whose committed metadata `token_id` shows once revealed.
+    fn asset_token(&self, token_id: &Id) -> Id {
+        token_id
+            .as_index()
+            .and_then(|index| Id::from_index(self.asset_index(index)))
+            .unwrap_or_else(|| token_id.clone())
     }
 
-    fn token_meta(&self, token_id: TokenId) -> Option<Meta> {
-        Metadata::instance().get(&token_id)
+    /// Index of the asset the token numbered `index` resolves to under
+    /// `base_uri`. Assumes ids run from zero when the collection was
+    /// revealed with an offset.
+    fn asset_index(&self, index: U256) -> U256 {
+        let supply = data::reveal_supply();
+        match data::reveal_offset() {
+            Some(offset) if !supply.is_zero() && index < supply => (index + offset) % supply,
+            _ => index,
+        }
     }
 
-    fn set_token_meta(&mut self, token_id: TokenId, meta: Meta) -> Result<(), Error> {
-        if self.owner_of(token_id).is_none() {
+    fn set_token_meta(&mut self, token_id: Id, meta: Meta) -> Result<(), Error> {
+        if self.owner_of(token_id.clone()).is_none() {
             return Err(Error::TokenIdDoesntExist);
         };
+        // Metadata written before the reveal would be public.
+        if !self.is_revealed() {
+            return Err(Error::NotRevealed);
+        }
+        if self.is_meta_frozen(token_id.clone()) {
+            return Err(Error::MetadataFrozen);
+        }
+        self.validate_meta(&meta)?;
 
-        let metadata_dict = Metadata::instance();
+        let metadata_dict = Metadata::<Id>::instance();
+        let previous = metadata_dict.get(&token_id).unwrap_or_default();
+        let changed_keys = changed_keys(&previous, &meta);
+        let versions_dict = MetadataVersions::<Id>::instance();
+        let previous_version = versions_dict.get(&token_id);
+        let version = previous_version.checked_add(1).unwrap_or_revert();
+        if data::keep_meta_history() {
+            MetadataHistory::instance().set(&token_id, previous_version, previous);
+        }
+        versions_dict.set(&token_id, version);
         metadata_dict.set(&token_id, meta);
 
-        self.emit(CEP47Event::MetadataUpdate { token_id });
+        self.emit(CEP47Event::MetadataUpdate {
+            token_id,
+            changed_keys,
+            version,
+        });
         Ok(())
     }
 
-    fn get_token_by_index(&self, owner: Key, index: U256) -> Option<TokenId> {
-        OwnedTokens::instance().get_token_by_index(&owner, &index)
+    fn keep_meta_history(&self) -> bool {
+        data::keep_meta_history()
+    }
+
+    fn set_keep_meta_history(&mut self, keep: bool) {
+        data::set_keep_meta_history(keep);
+    }
+
+    /// Versions the token's metadata went through, the current one included.
+    fn token_meta_version_count(&self, token_id: Id) -> u32 {
+        match self.owner_of(token_id.clone()) {
+            Some(_) => MetadataVersions::<Id>::instance().get(&token_id) + 1,
+            None => 0,
+        }
+    }
+
+    /// Metadata the token had at `version`. Versions superseded while
+    /// history was off are gone.
+    fn token_meta_at(&self, token_id: Id, version: u32) -> Option<Meta> {
+        self.owner_of(token_id.clone())?;
+        if !self.is_revealed() {
+            return data::placeholder_meta();
+        }
+        let current = MetadataVersions::<Id>::instance().get(&token_id);
+        if version == current {
+            self.token_meta(token_id)
+        } else if version < current {
+            MetadataHistory::instance().get(&token_id, version)
+        } else {
+            None
+        }
+    }
+
+    /// Merges `set` into the token's metadata and drops the `remove` keys,
+    /// leaving every other key as it is.
+    fn update_token_meta_keys(
+        &mut self,
+        token_id: Id,
+        set: Meta,
+        remove: Vec<String>,
+    ) -> Result<(), Error> {
+        if remove.iter().any(|key| set.contains_key(key)) {
+            return Err(Error::WrongArguments);
+        }
+        let mut meta = Metadata::<Id>::instance()
+            .get(&token_id)
+            .ok_or(Error::TokenIdDoesntExist)?;
+        for key in &remove {
+            meta.remove(key);
+        }
+        meta.extend(set);
+        self.set_token_meta(token_id, meta)
+    }
+
+    fn meta_schema(&self) -> Option<MetaSchema> {
+        data::meta_schema()
+    }
+
+    fn set_meta_schema(&mut self, schema: Option<MetaSchema>) {
+        data::set_meta_schema(schema);
+    }
+
+    /// Checks `meta` against the collection's schema, if it has one.
+    fn validate_meta(&self, meta: &Meta) -> Result<(), Error> {
+        match data::meta_schema() {
+            Some(schema) => schema.validate(meta),
+            None => Ok(()),
+        }
+    }
+
+    fn base_uri(&self) -> String {
+        data::base_uri()
+    }
+
+    fn set_base_uri(&mut self, base_uri: String) -> Result<(), Error> {
+        if data::all_meta_frozen() {
+            return Err(Error::MetadataFrozen);
+        }
+        data::set_base_uri(base_uri.clone());
+        self.emit(CEP47Event::BaseUriUpdate { base_uri });
+        Ok(())
     }
 
-    fn validate_token_ids(&self, token_ids: Vec<TokenId>) -> bool {
+    /// The token's own URI when it has one, otherwise `base_uri` followed by
+    /// the token's asset index, or by the id itself for ids that aren't
+    /// numbers. `None` for unknown tokens, before the reveal and when there is
+    /// no base URI.
+    fn token_uri(&self, token_id: Id) -> Option<String> {
+        self.owner_of(token_id.clone())?;
+        if !self.is_revealed() {
+            return None;
+        }
+        if let Some(uri) = TokenUris::<Id>::instance().get(&token_id) {
+            return Some(uri);
+        }
+        let base_uri = data::base_uri();
+        if base_uri.is_empty() {
+            return None;
+        }
+        match token_id.as_index() {
+            Some(index) => Some(format!("{}{}", base_uri, self.asset_index(index))),
+            None => Some(format!("{}{}", base_uri, token_id)),
+        }
+    }
+
+    /// Sets or, with `None`, clears the URI overriding the derived one.
+    fn set_token_uri(&mut self, token_id: Id, uri: Option<String>) -> Result<(), Error> {
+        if self.owner_of(token_id.clone()).is_none() {
+            return Err(Error::TokenIdDoesntExist);
+        }
+        if self.is_meta_frozen(token_id.clone()) {
+            return Err(Error::MetadataFrozen);
+        }
+        let token_uris_dict = TokenUris::<Id>::instance();
+        match uri {
+            Some(uri) => token_uris_dict.set(&token_id, uri),
+            None => token_uris_dict.remove(&token_id),
+        }
+        self.emit(CEP47Event::TokenUriUpdate { token_id });
+        Ok(())
+    }
+
+    fn token_checksum(&self, token_id: Id) -> Option<Checksum> {
+        Checksums::<Id>::instance().get(&token_id)
+    }
+
+    /// Updates the metadata together with the checksum of the content it
+    /// points to. `set_token_meta` alone keeps the recorded checksum.
+    fn set_token_meta_and_checksum(
+        &mut self,
+        token_id: Id,
+        meta: Meta,
+        checksum: Checksum,
+    ) -> Result<(), Error> {
+        validate_checksum(&checksum)?;
+        self.set_token_meta(token_id.clone(), meta)?;
+        Checksums::<Id>::instance().set(&token_id, checksum.clone());
+        self.emit(CEP47Event::ChecksumUpdate { token_id, checksum });
+        Ok(())
+    }
+
+    fn is_meta_frozen(&self, token_id: Id) -> bool {
+        data::all_meta_frozen() || FrozenMetadata::<Id>::instance().is_frozen(&token_id)
+    }
+
+    /// Makes the metadata of existing `token_ids` permanent. There is no way
+    /// back.
+    fn freeze_token_meta(&mut self, token_ids: Vec<Id>) -> Result<(), Error> {
+        for token_id in &token_ids {
+            if self.owner_of(token_id.clone()).is_none() {
+                return Err(Error::TokenIdDoesntExist);
+            }
+        }
+        let frozen_metadata_dict = FrozenMetadata::<Id>::instance();
+        for token_id in &token_ids {
+            frozen_metadata_dict.freeze(token_id);
+        }
+        self.emit(CEP47Event::PermanentMetadata { token_ids });
+        Ok(())
+    }
+
+    /// Makes the metadata of every token, including ones minted later,
+    /// permanent.
+    fn freeze_all_meta(&mut self) {
+        data::set_all_meta_frozen(true);
+        self.emit(CEP47Event::PermanentAllMetadata);
+    }
+
+    /// Tokens a wallet may mint through sales and phases, see
+    /// `record_wallet_mints`. Admin mints are exempt, see
+    /// `is_mint_limit_exempt`, so this is not a cap on holdings.
+    fn max_mint_per_wallet(&self) -> Option<u32> {
+        data::max_mint_per_wallet()
+    }
+
+    fn set_max_mint_per_wallet(&mut self, limit: Option<u32>) {
+        data::set_max_mint_per_wallet(limit);
+    }
+
+    fn burn_policy(&self) -> BurnPolicy {
+        data::burn_policy()
+    }
+
+    fn set_burn_policy(&mut self, policy: BurnPolicy) {
+        data::set_burn_policy(policy);
+    }
+
+    fn burned_supply(&self) -> U256 {
+        data::burned_supply()
+    }
+
+    fn is_burned(&self, token_id: Id) -> bool {
+        BurnedTokens::<Id>::instance().get(&token_id).is_some()
+    }
+
+    fn forbid_remint(&self) -> bool {
+        data::forbid_remint()
+    }
+
+    fn set_forbid_remint(&mut self, forbid: bool) {
+        data::set_forbid_remint(forbid);
+    }
+
+    fn wallet_minted(&self, account: Key) -> u32 {
+        WalletMints::instance().get(&account)
+    }
+
+    /// Accounts, such as collection admins, whose mints are neither counted
+    /// nor capped by `max_mint_per_wallet`.
+    fn is_mint_limit_exempt(&self, _account: Key) -> bool {
+        false
+    }
+
+    /// Counts `count` new tokens against both the caller and the recipient,
+    /// so the cap holds whichever side a buyer varies.
+    fn record_wallet_mints(
+        &mut self,
+        caller: Key,
+        recipient: Key,
+        count: u32,
+    ) -> Result<(), Error> {
+        let limit = data::max_mint_per_wallet();
+        let wallet_mints_dict = WalletMints::instance();
+        let mut wallets = vec![caller];
+        if recipient != caller {
+            wallets.push(recipient);
+        }
+        for wallet in &wallets {
+            let minted = wallet_mints_dict
+                .get(wallet)
+                .checked_add(count)
+                .ok_or(Error::MintLimitExceeded)?;
+            if matches!(limit, Some(limit) if minted > limit) {
+                return Err(Error::MintLimitExceeded);
+            }
+            wallet_mints_dict.set(wallet, minted);
+        }
+        Ok(())
+    }
+
+    fn recipient_policy(&self) -> RecipientPolicy {
+        data::recipient_policy()
+    }
+
+    fn set_recipient_policy(&mut self, policy: RecipientPolicy) {
+        data::set_recipient_policy(policy);
+    }
+
+    /// Checks `recipient` is a kind of key the recipient policy accepts and
+    /// not this contract, which only holds tokens attached to a parent.
+    fn validate_recipient(&self, recipient: Key) -> Result<(), Error> {
+        let allowed = match recipient {
+            Key::Account(_) => true,
+            Key::Hash(_) => self.recipient_policy() == RecipientPolicy::AccountsAndContracts,
+            _ => false,
+        };
+        if !allowed || recipient == self.self_addr() {
+            return Err(Error::InvalidRecipient);
+        }
+        Ok(())
+    }
+
+    fn get_token_by_index(&self, owner: Key, index: U256) -> Option<Id> {
+        OwnedTokens::<Id>::instance().get_token_by_index(&owner, &index)
+    }
+
+    fn validate_token_ids(&self, token_ids: Vec<Id>) -> bool {
         for token_id in &token_ids {
-            if self.owner_of(*token_id).is_some() {
+            if self.owner_of(token_id.clone()).is_some() {
                 return false;
             }
         }
@@ -90,25 +809,51 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
     fn mint(
         &mut self,
         recipient: Key,
-        token_ids: Vec<TokenId>,
+        token_ids: Vec<Id>,
         token_metas: Vec<Meta>,
-    ) -> Result<Vec<TokenId>, Error> {
+    ) -> Result<Vec<Id>, Error> {
         if token_ids.len() != token_metas.len() {
             return Err(Error::WrongArguments);
         };
+        self.validate_recipient(recipient)?;
 
+        let forbid_remint = data::forbid_remint();
         for token_id in &token_ids {
-            if self.owner_of(*token_id).is_some() {
+            if self.owner_of(token_id.clone()).is_some() {
                 return Err(Error::TokenIdAlreadyExists);
             }
+            if forbid_remint && self.is_burned(token_id.clone()) {
+                return Err(Error::TokenIdBurned);
+            }
+        }
+        if let Some(schema) = data::meta_schema() {
+            for token_meta in &token_metas {
+                schema.validate(token_meta)?;
+            }
         }
 
-        let owners_dict = Owners::instance();
-        let owned_tokens_dict = OwnedTokens::instance();
-        let metadata_dict = Metadata::instance();
+        let caller = self.get_caller();
+        if !self.is_mint_limit_exempt(caller) {
+            self.record_wallet_mints(caller, recipient, token_ids.len().try_into().unwrap())?;
+        }
+
+        let owners_dict = Owners::<Id>::instance();
+        let owned_tokens_dict = OwnedTokens::<Id>::instance();
+        let metadata_dict = Metadata::<Id>::instance();
+        let burned_tokens_dict = BurnedTokens::<Id>::instance();
+        let commitments_dict = MetaCommitments::<Id>::instance();
+        let revealed = self.is_revealed();
 
         for (token_id, token_meta) in token_ids.iter().zip(&token_metas) {
-            metadata_dict.set(token_id, token_meta.clone());
+            // A re-minted id is live again; `burned_supply` keeps counting the burn.
+            if burned_tokens_dict.get(token_id).is_some() {
+                burned_tokens_dict.remove(token_id);
+            }
+            if revealed {
+                metadata_dict.set(token_id, token_meta.clone());
+            } else {
+                commitments_dict.set(token_id, meta_commitment(token_meta));
+            }
             owners_dict.set(token_id, recipient);
             owned_tokens_dict.set_token(&recipient, token_id);
         }
@@ -126,34 +871,92 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         Ok(token_ids)
     }
 
+    /// Mints like `mint` and records a content checksum for every token.
+    fn mint_with_checksums(
+        &mut self,
+        recipient: Key,
+        token_ids: Vec<Id>,
+        token_metas: Vec<Meta>,
+        token_checksums: Vec<Checksum>,
+    ) -> Result<Vec<Id>, Error> {
+        if token_ids.len() != token_checksums.len() {
+            return Err(Error::WrongArguments);
+        }
+        for checksum in &token_checksums {
+            validate_checksum(checksum)?;
+        }
+        let token_ids = self.mint(recipient, token_ids, token_metas)?;
+        let checksums_dict = Checksums::<Id>::instance();
+        for (token_id, checksum) in token_ids.iter().zip(token_checksums) {
+            checksums_dict.set(token_id, checksum.clone());
+            self.emit(CEP47Event::ChecksumUpdate {
+                token_id: token_id.clone(),
+                checksum,
+            });
+        }
+        Ok(token_ids)
+    }
+
     fn mint_copies(
         &mut self,
         recipient: Key,
-        token_ids: Vec<TokenId>,
+        token_ids: Vec<Id>,
         token_meta: Meta,
         count: u32,
-    ) -> Result<Vec<TokenId>, Error> {
+    ) -> Result<Vec<Id>, Error> {
         let token_metas = vec![token_meta; count.try_into().unwrap()];
         self.mint(recipient, token_ids, token_metas)
     }
 
-    fn burn(&mut self, owner: Key, token_ids: Vec<TokenId>) -> Result<(), Error> {
+    fn burn(&mut self, owner: Key, token_ids: Vec<Id>) -> Result<(), Error> {
         let spender = self.get_caller();
-        if spender != owner {
-            for token_id in &token_ids {
-                if !self.is_approved(owner, *token_id, spender) {
+        match self.burn_policy() {
+            BurnPolicy::Disabled => return Err(Error::BurnDisabled),
+            BurnPolicy::Admin => return Err(Error::PermissionDenied),
+            BurnPolicy::OwnerOnly => {
+                if spender != owner {
                     return Err(Error::PermissionDenied);
                 }
             }
+            BurnPolicy::OwnerOrApproved => {
+                if spender != owner {
+                    for token_id in &token_ids {
+                        if !self.is_approved(owner, token_id.clone(), spender) {
+                            return Err(Error::PermissionDenied);
+                        }
+                    }
+                }
+            }
         }
         self.burn_internal(owner, token_ids)
     }
 
-    fn burn_internal(&mut self, owner: Key, token_ids: Vec<TokenId>) -> Result<(), Error> {
-        let owners_dict = Owners::instance();
-        let owned_tokens_dict = OwnedTokens::instance();
-        let metadata_dict = Metadata::instance();
+    /// Burns on behalf of the collection under the `Admin` policy. Checking
+    /// that the caller is an admin is left to the implementing contract.
+    fn burn_as_admin(&mut self, owner: Key, token_ids: Vec<Id>) -> Result<(), Error> {
+        match self.burn_policy() {
+            BurnPolicy::Admin => self.burn_internal(owner, token_ids),
+            BurnPolicy::Disabled => Err(Error::BurnDisabled),
+            _ => Err(Error::PermissionDenied),
+        }
+    }
+
+    fn burn_internal(&mut self, owner: Key, token_ids: Vec<Id>) -> Result<(), Error> {
+        let owners_dict = Owners::<Id>::instance();
+        let owned_tokens_dict = OwnedTokens::<Id>::instance();
+        let metadata_dict = Metadata::<Id>::instance();
         let allowances_dict = Allowances::instance();
+        let children_dict = Children::<Id>::instance();
+        let burned_tokens_dict = BurnedTokens::<Id>::instance();
+        let versions_dict = MetadataVersions::<Id>::instance();
+        let token_uris_dict = TokenUris::<Id>::instance();
+        let checksums_dict = Checksums::<Id>::instance();
+        let parents_dict = Parents::<Id>::instance();
+        let frozen_metadata_dict = FrozenMetadata::<Id>::instance();
+        let history_dict = MetadataHistory::instance();
+        let commitments_dict = MetaCommitments::<Id>::instance();
+        // After the reveal a commitment belongs to the token shown with it.
+        let revealed = self.is_revealed();
 
         for token_id in &token_ids {
             match owners_dict.get(token_id) {
@@ -166,13 +969,34 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
                     return Err(Error::TokenIdDoesntExist);
                 }
             }
+            if !children_dict.get(token_id).is_empty() {
+                return Err(Error::TokenHasChildren);
+            }
+            // An attached token is held by the contract; detach it first.
+            if parents_dict.get(token_id).is_some() {
+                return Err(Error::TokenIsAttached);
+            }
         }
 
         for token_id in &token_ids {
             owned_tokens_dict.remove_token(&owner, token_id);
             metadata_dict.remove(token_id);
+            if !revealed && commitments_dict.get(token_id).is_some() {
+                commitments_dict.remove(token_id);
+            }
             owners_dict.remove(token_id);
             allowances_dict.remove(&owner, token_id);
+            burned_tokens_dict.set(token_id, owner);
+            // A re-minted id starts its metadata history over, unfrozen.
+            history_dict.clear(token_id, versions_dict.get(token_id));
+            versions_dict.remove(token_id);
+            frozen_metadata_dict.unfreeze(token_id);
+            if token_uris_dict.get(token_id).is_some() {
+                token_uris_dict.remove(token_id);
+            }
+            if checksums_dict.get(token_id).is_some() {
+                checksums_dict.remove(token_id);
+            }
         }
 
         let burnt_tokens_count: U256 = From::<u64>::from(token_ids.len().try_into().unwrap());
@@ -180,15 +1004,16 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
             .checked_sub(burnt_tokens_count)
             .unwrap();
         data::set_total_supply(new_total_supply);
+        data::set_burned_supply(data::burned_supply() + burnt_tokens_count);
 
         self.emit(CEP47Event::Burn { owner, token_ids });
         Ok(())
     }
 
-    fn approve(&mut self, spender: Key, token_ids: Vec<TokenId>) -> Result<(), Error> {
+    fn approve(&mut self, spender: Key, token_ids: Vec<Id>) -> Result<(), Error> {
         let caller = self.get_caller();
         for token_id in &token_ids {
-            match self.owner_of(*token_id) {
+            match self.owner_of(token_id.clone()) {
                 None => return Err(Error::WrongArguments),
                 Some(owner) if owner != caller => return Err(Error::PermissionDenied),
                 Some(_) => Allowances::instance().set(&caller, token_id, spender),
@@ -202,10 +1027,10 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         Ok(())
     }
 
-    fn revoke(&mut self, token_ids: Vec<TokenId>) -> Result<(), Error> {
+    fn revoke(&mut self, token_ids: Vec<Id>) -> Result<(), Error> {
         let caller = self.get_caller();
         for token_id in &token_ids {
-            match self.owner_of(*token_id) {
+            match self.owner_of(token_id.clone()) {
                 None => return Err(Error::WrongArguments),
                 Some(owner) if owner != caller => return Err(Error::PermissionDenied),
                 Some(_) => Allowances::instance().remove(&caller, token_id),
@@ -218,11 +1043,11 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         Ok(())
     }
 
-    fn get_approved(&self, owner: Key, token_id: TokenId) -> Option<Key> {
+    fn get_approved(&self, owner: Key, token_id: Id) -> Option<Key> {
         Allowances::instance().get(&owner, &token_id)
     }
 
-    fn transfer(&mut self, recipient: Key, token_ids: Vec<TokenId>) -> Result<(), Error> {
+    fn transfer(&mut self, recipient: Key, token_ids: Vec<Id>) -> Result<(), Error> {
         self.transfer_from(self.get_caller(), recipient, token_ids)
     }
 
@@ -230,14 +1055,18 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         &mut self,
         owner: Key,
         recipient: Key,
-        token_ids: Vec<TokenId>,
+        token_ids: Vec<Id>,
     ) -> Result<(), Error> {
+        self.validate_recipient(recipient)?;
+        if recipient == owner {
+            return Err(Error::InvalidRecipient);
+        }
         let spender = self.get_caller();
 
         if owner != spender {
             let allowances_dict = Allowances::instance();
             for token_id in &token_ids {
-                if !self.is_approved(owner, *token_id, spender) {
+                if !self.is_approved(owner, token_id.clone(), spender) {
                     return Err(Error::PermissionDenied);
                 }
                 allowances_dict.remove(&owner, token_id);
@@ -250,12 +1079,17 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         &mut self,
         owner: Key,
         recipient: Key,
-        token_ids: Vec<TokenId>,
+        token_ids: Vec<Id>,
     ) -> Result<(), Error> {
-        let owners_dict = Owners::instance();
-        let owned_tokens_dict = OwnedTokens::instance();
+        let owners_dict = Owners::<Id>::instance();
+        let owned_tokens_dict = OwnedTokens::<Id>::instance();
+        let parents_dict = Parents::<Id>::instance();
 
         for token_id in &token_ids {
+            // Attached children only move together with their parent.
+            if parents_dict.get(token_id).is_some() {
+                return Err(Error::TokenIsAttached);
+            }
             match owners_dict.get(token_id) {
                 Some(owner_of_key) => {
                     if owner_of_key != owner {
@@ -282,7 +1116,91 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         Ok(())
     }
 
-    fn is_approved(&self, owner: Key, token_id: TokenId, spender: Key) -> bool {
+    fn parent_of(&self, token_id: Id) -> Option<Id> {
+        Parents::<Id>::instance().get(&token_id)
+    }
+
+    fn children_of(&self, token_id: Id) -> Vec<Id> {
+        Children::<Id>::instance().get(&token_id)
+    }
+
+    fn root_owner_of(&self, token_id: Id) -> Option<Key> {
+        let parents_dict = Parents::<Id>::instance();
+        let mut root_id = token_id;
+        while let Some(parent_id) = parents_dict.get(&root_id) {
+            root_id = parent_id;
+        }
+        self.owner_of(root_id)
+    }
+
+    fn attach_child(&mut self, parent_id: Id, token_ids: Vec<Id>) -> Result<(), Error> {
+        let caller = self.get_caller();
+        match self.root_owner_of(parent_id.clone()) {
+            None => return Err(Error::TokenIdDoesntExist),
+            Some(root_owner) if root_owner != caller => return Err(Error::PermissionDenied),
+            Some(_) => {}
+        }
+
+        let parents_dict = Parents::<Id>::instance();
+        for token_id in &token_ids {
+            let mut ancestor_id = Some(parent_id.clone());
+            while let Some(id) = ancestor_id {
+                if id == *token_id {
+                    return Err(Error::InvalidParent);
+                }
+                ancestor_id = parents_dict.get(&id);
+            }
+        }
+
+        // Attached children are held by the contract itself.
+        self.transfer_from_internal(caller, self.self_addr(), token_ids.clone())?;
+
+        let children_dict = Children::<Id>::instance();
+        for token_id in &token_ids {
+            parents_dict.set(token_id, parent_id.clone());
+            children_dict.add(&parent_id, token_id);
+        }
+
+        self.emit(CEP47Event::Attach {
+            owner: caller,
+            parent_id,
+            token_ids,
+        });
+        Ok(())
+    }
+
+    fn detach_child(&mut self, parent_id: Id, token_ids: Vec<Id>) -> Result<(), Error> {
+        let caller = self.get_caller();
+        match self.root_owner_of(parent_id.clone()) {
+            None => return Err(Error::TokenIdDoesntExist),
+            Some(root_owner) if root_owner != caller => return Err(Error::PermissionDenied),
+            Some(_) => {}
+        }
+
+        let parents_dict = Parents::<Id>::instance();
+        for token_id in &token_ids {
+            if parents_dict.get(token_id).as_ref() != Some(&parent_id) {
+                return Err(Error::TokenIsNotAttached);
+            }
+        }
+
+        let children_dict = Children::<Id>::instance();
+        for token_id in &token_ids {
+            parents_dict.remove(token_id);
+            children_dict.remove(&parent_id, token_id);
+        }
+
+        self.transfer_from_internal(self.self_addr(), caller, token_ids.clone())?;
+
+        self.emit(CEP47Event::Detach {
+            owner: caller,
+            parent_id,
+            token_ids,
+        });
+        Ok(())
+    }
+
+    fn is_approved(&self, owner: Key, token_id: Id, spender: Key) -> bool {
         let allowances_dict = Allowances::instance();
         if let Some(spender_of) = allowances_dict.get(&owner, &token_id) {
             if spender_of == spender {
@@ -292,7 +1210,43 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         false
     }
 
-    fn emit(&mut self, event: CEP47Event) {
+    fn emit(&mut self, event: CEP47Event<Id>) {
         data::emit(&event);
     }
 }
+
+/// Keys added, removed or given a new value going from `previous` to `meta`.
+/// Hex blake2b digest of `meta`'s bytesrepr.
+fn meta_commitment(meta: &Meta) -> String {
+    hex::encode(runtime::blake2b(meta.to_bytes().unwrap_or_revert()))
+}
+
+fn changed_keys(previous: &Meta, meta: &Meta) -> Vec<String> {
+    let mut keys: Vec<String> = meta
+        .iter()
+        .filter(|(key, value)| previous.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    keys.extend(
+        previous
+            .keys()
+            .filter(|key| !meta.contains_key(*key))
+            .cloned(),
+    );
+    keys.sort();
+    keys
+}
+
+/// Accepts 256 and 512 bit blake2b and sha2 digests, hex encoded.
+fn validate_checksum(checksum: &Checksum) -> Result<(), Error> {
+    let (algorithm, digest) = checksum;
+    let digest_length = match algorithm.as_str() {
+        "blake2b-256" | "sha2-256" => 64,
+        "blake2b-512" | "sha2-512" => 128,
+        _ => return Err(Error::InvalidChecksum),
+    };
+    if digest.len() != digest_length || !digest.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(Error::InvalidChecksum);
+    }
+    Ok(())
+}
@@ -0,0 +1,1509 @@
+//! Entry points of the CEP47 contract, generic over the token identifier.
+//! A contract binary exports them for its id type with [`cep47_contract!`].
+
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::AccountHash, contracts::NamedKeys, runtime_args, ApiError, CLType, CLTyped, CLValue,
+    ContractHash, ContractPackageHash, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+    Group, Key, Parameter, RuntimeArgs, URef, U256, U512,
+};
+use contract_utils::{
+    get_optional_named_arg, AdminControl, ContractContext, OnChainContractStorage,
+};
+use core::{convert::TryFrom, marker::PhantomData};
+
+use crate::{
+    data, BurnPolicy, Checksum, Meta, MetaSchema, MintPhase, Phases, RecipientPolicy, Sale,
+    SaleItem, TokenId, TokenIdentifier, CEP47,
+};
+
+/// Exports every entry point of [`contract`](crate::contract), and `call`
+/// installing or upgrading it, for tokens identified by `$id`.
+#[macro_export]
+macro_rules! cep47_contract {
+    ($id:ty) => {
+        $crate::cep47_contract!(
+            $id;
+            constructor, migrate, migrate_token_keys, finish_token_key_migration,
+            migrate_account_keys, finish_account_key_migration, name, symbol, meta, set_name,
+            set_symbol, set_meta, update_meta_keys, total_supply, balance_of, get_token_by_index,
+            owner_of, token_meta, token_meta_version_count, token_meta_at, update_token_meta,
+            update_token_meta_and_checksum, token_checksum, update_token_meta_keys, is_meta_frozen,
+            freeze_token_meta, freeze_all_meta, base_uri, set_base_uri, token_uri, set_token_uri,
+            provenance_hash, is_revealed, reveal, reveal_token_meta, mint, mint_with_checksums,
+            mint_copies, max_mint_per_wallet, set_max_mint_per_wallet, wallet_minted, purchase_mint,
+            sale_item, list_sale_items, unlist_sale_items, mint_price, set_mint_price, tier_price,
+            set_tier_price, set_treasury, withdraw, set_mint_phase, remove_mint_phase,
+            set_allowlist, is_allowlisted, phase_minted, phase_mint, add_admin, disable_admin, burn,
+            burn_as_admin, burn_policy, recipient_policy, burned_supply, is_burned, transfer,
+            transfer_from, approve, revoke, get_approved, parent_of, children_of, root_owner_of,
+            attach_child, detach_child, call
+        );
+    };
+    ($id:ty; $($entry_point:ident),*) => {
+        $(
+            #[no_mangle]
+            fn $entry_point() {
+                $crate::contract::$entry_point::<$id>()
+            }
+        )*
+    };
+}
+
+/// The contract as installed, with tokens identified by `Id`.
+pub struct NFTToken<Id = TokenId>(OnChainContractStorage, PhantomData<Id>);
+
+impl<Id> Default for NFTToken<Id> {
+    fn default() -> Self {
+        NFTToken(OnChainContractStorage::default(), PhantomData)
+    }
+}
+
+impl<Id> ContractContext<OnChainContractStorage> for NFTToken<Id> {
+    fn storage(&self) -> &OnChainContractStorage {
+        &self.0
+    }
+}
+
+impl<Id: TokenIdentifier> CEP47<OnChainContractStorage, Id> for NFTToken<Id> {
+    // Only admins may call `mint`, so the cap applies to `purchase_mint` and
+    // `phase_mint`.
+    fn is_mint_limit_exempt(&self, account: Key) -> bool {
+        self.is_admin(account)
+    }
+}
+impl<Id: TokenIdentifier> Sale<OnChainContractStorage, Id> for NFTToken<Id> {}
+impl<Id: TokenIdentifier> Phases<OnChainContractStorage, Id> for NFTToken<Id> {}
+impl<Id> AdminControl<OnChainContractStorage> for NFTToken<Id> {}
+impl<Id: TokenIdentifier> NFTToken<Id> {
+    #[allow(clippy::too_many_arguments)]
+    fn constructor(
+        &mut self,
+        name: String,
+        symbol: String,
+        meta: Meta,
+        burn_policy: BurnPolicy,
+        recipient_policy: RecipientPolicy,
+        forbid_remint: bool,
+        meta_schema: Option<MetaSchema>,
+        keep_meta_history: bool,
+        base_uri: String,
+        pending_reveal: Option<(String, Meta)>,
+        lock_collection_meta: bool,
+    ) {
+        CEP47::init(self, name, symbol, meta);
+        self.set_burn_policy(burn_policy);
+        self.set_recipient_policy(recipient_policy);
+        self.set_forbid_remint(forbid_remint);
+        self.set_meta_schema(meta_schema);
+        self.set_keep_meta_history(keep_meta_history);
+        data::set_base_uri(base_uri);
+        if let Some((provenance_hash, placeholder)) = pending_reveal {
+            self.set_pending_reveal(provenance_hash, placeholder);
+        }
+        if lock_collection_meta {
+            self.lock_collection_meta();
+        }
+        AdminControl::init(self);
+        self.add_admin_without_checked(self.get_caller());
+    }
+}
+
+pub fn constructor<Id: TokenIdentifier>() {
+    let name = runtime::get_named_arg::<String>("name");
+    let symbol = runtime::get_named_arg::<String>("symbol");
+    let meta = runtime::get_named_arg::<Meta>("meta");
+    let burn_policy =
+        BurnPolicy::try_from(runtime::get_named_arg::<u8>("burn_policy")).unwrap_or_revert();
+    let recipient_policy =
+        RecipientPolicy::try_from(runtime::get_named_arg::<u8>("recipient_policy"))
+            .unwrap_or_revert();
+    let forbid_remint = runtime::get_named_arg::<bool>("forbid_remint");
+    let meta_schema = runtime::get_named_arg::<Option<MetaSchema>>("meta_schema");
+    let keep_meta_history = runtime::get_named_arg::<bool>("keep_meta_history");
+    let base_uri = runtime::get_named_arg::<String>("base_uri");
+    let pending_reveal = runtime::get_named_arg::<Option<(String, Meta)>>("pending_reveal");
+    let lock_collection_meta = runtime::get_named_arg::<bool>("lock_collection_meta");
+    NFTToken::<Id>::default().constructor(
+        name,
+        symbol,
+        meta,
+        burn_policy,
+        recipient_policy,
+        forbid_remint,
+        meta_schema,
+        keep_meta_history,
+        base_uri,
+        pending_reveal,
+        lock_collection_meta,
+    );
+}
+
+pub fn migrate_token_keys<Id: TokenIdentifier>() {
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.migrate_token_keys(token_ids);
+}
+
+pub fn finish_token_key_migration<Id: TokenIdentifier>() {
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.finish_token_key_migration();
+}
+
+pub fn migrate_account_keys<Id: TokenIdentifier>() {
+    let accounts = runtime::get_named_arg::<Vec<Key>>("accounts");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    for account in &accounts {
+        token.migrate_admin_key(*account);
+    }
+    token.migrate_account_keys(accounts);
+}
+
+pub fn finish_account_key_migration<Id: TokenIdentifier>() {
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.finish_account_key_migration();
+}
+
+pub fn name<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().name();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn symbol<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().symbol();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn meta<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().meta();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn set_name<Id: TokenIdentifier>() {
+    let name = runtime::get_named_arg::<String>("name");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.set_name(name).unwrap_or_revert();
+}
+
+pub fn set_symbol<Id: TokenIdentifier>() {
+    let symbol = runtime::get_named_arg::<String>("symbol");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.set_symbol(symbol).unwrap_or_revert();
+}
+
+pub fn set_meta<Id: TokenIdentifier>() {
+    let meta = runtime::get_named_arg::<Meta>("meta");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.set_meta(meta).unwrap_or_revert();
+}
+
+pub fn update_meta_keys<Id: TokenIdentifier>() {
+    let set = runtime::get_named_arg::<Meta>("set");
+    let remove = runtime::get_named_arg::<Vec<String>>("remove");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.update_meta_keys(set, remove).unwrap_or_revert();
+}
+
+pub fn total_supply<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().total_supply();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn balance_of<Id: TokenIdentifier>() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let ret = NFTToken::<Id>::default().balance_of(owner);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn get_token_by_index<Id: TokenIdentifier>() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let index = runtime::get_named_arg::<U256>("index");
+    let ret = NFTToken::<Id>::default().get_token_by_index(owner, index);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn owner_of<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().owner_of(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn token_meta<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().token_meta(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn token_meta_version_count<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().token_meta_version_count(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn token_meta_at<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let version = runtime::get_named_arg::<u32>("version");
+    let ret = NFTToken::<Id>::default().token_meta_at(token_id, version);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn update_token_meta<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let token_meta = runtime::get_named_arg::<Meta>("token_meta");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token
+        .set_token_meta(token_id, token_meta)
+        .unwrap_or_revert();
+}
+
+pub fn update_token_meta_and_checksum<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let token_meta = runtime::get_named_arg::<Meta>("token_meta");
+    let token_checksum = runtime::get_named_arg::<Checksum>("token_checksum");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token
+        .set_token_meta_and_checksum(token_id, token_meta, token_checksum)
+        .unwrap_or_revert();
+}
+
+pub fn token_checksum<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().token_checksum(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn update_token_meta_keys<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let set = runtime::get_named_arg::<Meta>("set");
+    let remove = runtime::get_named_arg::<Vec<String>>("remove");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token
+        .update_token_meta_keys(token_id, set, remove)
+        .unwrap_or_revert();
+}
+
+pub fn is_meta_frozen<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().is_meta_frozen(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn freeze_token_meta<Id: TokenIdentifier>() {
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.freeze_token_meta(token_ids).unwrap_or_revert();
+}
+
+pub fn freeze_all_meta<Id: TokenIdentifier>() {
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.freeze_all_meta();
+}
+
+pub fn base_uri<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().base_uri();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn set_base_uri<Id: TokenIdentifier>() {
+    let base_uri = runtime::get_named_arg::<String>("base_uri");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.set_base_uri(base_uri).unwrap_or_revert();
+}
+
+pub fn token_uri<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().token_uri(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn set_token_uri<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let uri = runtime::get_named_arg::<Option<String>>("uri");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.set_token_uri(token_id, uri).unwrap_or_revert();
+}
+
+pub fn provenance_hash<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().provenance_hash();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn is_revealed<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().is_revealed();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn reveal<Id: TokenIdentifier>() {
+    let base_uri = runtime::get_named_arg::<String>("base_uri");
+    let offset_seed = runtime::get_named_arg::<U256>("offset_seed");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.reveal(base_uri, offset_seed).unwrap_or_revert();
+}
+
+pub fn reveal_token_meta<Id: TokenIdentifier>() {
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    let token_metas = runtime::get_named_arg::<Vec<Meta>>("token_metas");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token
+        .reveal_token_meta(token_ids, token_metas)
+        .unwrap_or_revert();
+}
+
+pub fn mint<Id: TokenIdentifier>() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    let token_metas = runtime::get_named_arg::<Vec<Meta>>("token_metas");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token
+        .mint(recipient, token_ids, token_metas)
+        .unwrap_or_revert();
+}
+
+pub fn mint_with_checksums<Id: TokenIdentifier>() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    let token_metas = runtime::get_named_arg::<Vec<Meta>>("token_metas");
+    let token_checksums = runtime::get_named_arg::<Vec<Checksum>>("token_checksums");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token
+        .mint_with_checksums(recipient, token_ids, token_metas, token_checksums)
+        .unwrap_or_revert();
+}
+
+pub fn mint_copies<Id: TokenIdentifier>() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    let token_meta = runtime::get_named_arg::<Meta>("token_meta");
+    let count = runtime::get_named_arg::<u32>("count");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token
+        .mint_copies(recipient, token_ids, token_meta, count)
+        .unwrap_or_revert();
+}
+
+pub fn max_mint_per_wallet<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().max_mint_per_wallet();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn set_max_mint_per_wallet<Id: TokenIdentifier>() {
+    let limit = runtime::get_named_arg::<Option<u32>>("limit");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.set_max_mint_per_wallet(limit);
+}
+
+pub fn wallet_minted<Id: TokenIdentifier>() {
+    let account = runtime::get_named_arg::<Key>("account");
+    let ret = NFTToken::<Id>::default().wallet_minted(account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn purchase_mint<Id: TokenIdentifier>() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    let purse = runtime::get_named_arg::<URef>("purse");
+    NFTToken::<Id>::default()
+        .purchase_mint(recipient, token_ids, purse)
+        .unwrap_or_revert();
+}
+
+pub fn sale_item<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().sale_item(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn list_sale_items<Id: TokenIdentifier>() {
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    let token_metas = runtime::get_named_arg::<Vec<Meta>>("token_metas");
+    let tier = runtime::get_named_arg::<Option<String>>("tier");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token
+        .list_sale_items(token_ids, token_metas, tier)
+        .unwrap_or_revert();
+}
+
+pub fn unlist_sale_items<Id: TokenIdentifier>() {
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.unlist_sale_items(token_ids);
+}
+
+pub fn mint_price<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().mint_price();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn set_mint_price<Id: TokenIdentifier>() {
+    let price = runtime::get_named_arg::<Option<U512>>("price");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.set_mint_price(price);
+}
+
+pub fn tier_price<Id: TokenIdentifier>() {
+    let tier = runtime::get_named_arg::<String>("tier");
+    let ret = NFTToken::<Id>::default().tier_price(tier);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn set_tier_price<Id: TokenIdentifier>() {
+    let tier = runtime::get_named_arg::<String>("tier");
+    let price = runtime::get_named_arg::<Option<U512>>("price");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.set_tier_price(tier, price);
+}
+
+pub fn set_treasury<Id: TokenIdentifier>() {
+    let treasury = runtime::get_named_arg::<AccountHash>("treasury");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.set_treasury(treasury);
+}
+
+pub fn withdraw<Id: TokenIdentifier>() {
+    let amount = runtime::get_named_arg::<U512>("amount");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.withdraw(amount);
+}
+
+pub fn set_mint_phase<Id: TokenIdentifier>() {
+    let phase = runtime::get_named_arg::<String>("phase");
+    let mint_phase = MintPhase {
+        start_time: runtime::get_named_arg("start_time"),
+        end_time: runtime::get_named_arg("end_time"),
+        wallet_limit: runtime::get_named_arg("wallet_limit"),
+        price: runtime::get_named_arg("price"),
+        merkle_root: runtime::get_named_arg("merkle_root"),
+    };
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.set_mint_phase(phase, mint_phase).unwrap_or_revert();
+}
+
+pub fn remove_mint_phase<Id: TokenIdentifier>() {
+    let phase = runtime::get_named_arg::<String>("phase");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.remove_mint_phase(phase);
+}
+
+pub fn set_allowlist<Id: TokenIdentifier>() {
+    let phase = runtime::get_named_arg::<String>("phase");
+    let accounts = runtime::get_named_arg::<Vec<Key>>("accounts");
+    let allowed = runtime::get_named_arg::<bool>("allowed");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.set_allowlist(phase, accounts, allowed);
+}
+
+pub fn is_allowlisted<Id: TokenIdentifier>() {
+    let phase = runtime::get_named_arg::<String>("phase");
+    let account = runtime::get_named_arg::<Key>("account");
+    let ret = NFTToken::<Id>::default().is_allowlisted(phase, account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn phase_minted<Id: TokenIdentifier>() {
+    let phase = runtime::get_named_arg::<String>("phase");
+    let account = runtime::get_named_arg::<Key>("account");
+    let ret = NFTToken::<Id>::default().phase_minted(phase, account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn phase_mint<Id: TokenIdentifier>() {
+    let phase = runtime::get_named_arg::<String>("phase");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    let proof = runtime::get_named_arg::<Vec<[u8; 32]>>("proof");
+    let purse = runtime::get_named_arg::<Option<URef>>("purse");
+    NFTToken::<Id>::default()
+        .phase_mint(phase, token_ids, proof, purse)
+        .unwrap_or_revert();
+}
+
+pub fn add_admin<Id: TokenIdentifier>() {
+    let address = runtime::get_named_arg::<Key>("address");
+    NFTToken::<Id>::default().add_admin(address);
+}
+
+pub fn disable_admin<Id: TokenIdentifier>() {
+    let address = runtime::get_named_arg::<Key>("address");
+    NFTToken::<Id>::default().disable_admin(address);
+}
+
+pub fn burn<Id: TokenIdentifier>() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    NFTToken::<Id>::default()
+        .burn(owner, token_ids)
+        .unwrap_or_revert();
+}
+
+pub fn burn_as_admin<Id: TokenIdentifier>() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    let mut token = NFTToken::<Id>::default();
+    token.assert_caller_is_admin();
+    token.burn_as_admin(owner, token_ids).unwrap_or_revert();
+}
+
+pub fn burn_policy<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().burn_policy() as u8;
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn recipient_policy<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().recipient_policy() as u8;
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn burned_supply<Id: TokenIdentifier>() {
+    let ret = NFTToken::<Id>::default().burned_supply();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn is_burned<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().is_burned(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn transfer<Id: TokenIdentifier>() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    NFTToken::<Id>::default()
+        .transfer(recipient, token_ids)
+        .unwrap_or_revert();
+}
+
+pub fn transfer_from<Id: TokenIdentifier>() {
+    let sender = runtime::get_named_arg::<Key>("sender");
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    NFTToken::<Id>::default()
+        .transfer_from(sender, recipient, token_ids)
+        .unwrap_or_revert();
+}
+
+pub fn approve<Id: TokenIdentifier>() {
+    let spender = runtime::get_named_arg::<Key>("spender");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    NFTToken::<Id>::default()
+        .approve(spender, token_ids)
+        .unwrap_or_revert();
+}
+
+pub fn revoke<Id: TokenIdentifier>() {
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    NFTToken::<Id>::default()
+        .revoke(token_ids)
+        .unwrap_or_revert();
+}
+
+pub fn get_approved<Id: TokenIdentifier>() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().get_approved(owner, token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn parent_of<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().parent_of(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn children_of<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().children_of(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn root_owner_of<Id: TokenIdentifier>() {
+    let token_id = runtime::get_named_arg::<Id>("token_id");
+    let ret = NFTToken::<Id>::default().root_owner_of(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+pub fn attach_child<Id: TokenIdentifier>() {
+    let parent_id = runtime::get_named_arg::<Id>("parent_id");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    NFTToken::<Id>::default()
+        .attach_child(parent_id, token_ids)
+        .unwrap_or_revert();
+}
+
+pub fn detach_child<Id: TokenIdentifier>() {
+    let parent_id = runtime::get_named_arg::<Id>("parent_id");
+    let token_ids = runtime::get_named_arg::<Vec<Id>>("token_ids");
+    NFTToken::<Id>::default()
+        .detach_child(parent_id, token_ids)
+        .unwrap_or_revert();
+}
+
+pub fn migrate<Id: TokenIdentifier>() {
+    let mut token = NFTToken::<Id>::default();
+    // Versions before 3 were installed without admins; the upgrader becomes one.
+    let needs_admins = data::storage_version() < 3;
+    token.migrate().unwrap_or_revert();
+    if needs_admins {
+        AdminControl::init(&mut token);
+        token.add_admin_without_checked(token.get_caller());
+    }
+}
+
+pub fn call<Id: TokenIdentifier>() {
+    let contract_name: String = runtime::get_named_arg("contract_name");
+
+    // A package installed under this name means we ship a new version of it.
+    match runtime::get_key(&format!("{}_contract_package_hash", contract_name)) {
+        Some(package_key) => upgrade::<Id>(contract_name, package_key),
+        None => install::<Id>(contract_name),
+    }
+}
+
+fn install<Id: TokenIdentifier>(contract_name: String) {
+    // Read arguments for the constructor call.
+    let name: String = runtime::get_named_arg("name");
+    let symbol: String = runtime::get_named_arg("symbol");
+    let meta: Meta = runtime::get_named_arg("meta");
+    let burn_policy: u8 =
+        get_optional_named_arg("burn_policy").unwrap_or(BurnPolicy::OwnerOrApproved as u8);
+    let recipient_policy: u8 = get_optional_named_arg("recipient_policy")
+        .unwrap_or(RecipientPolicy::AccountsAndContracts as u8);
+    let forbid_remint: bool = get_optional_named_arg("forbid_remint").unwrap_or_default();
+    let meta_schema = meta_schema_arg();
+    let keep_meta_history: bool = get_optional_named_arg("keep_meta_history").unwrap_or_default();
+    let base_uri: String = get_optional_named_arg("base_uri").unwrap_or_default();
+    // Hides token metadata behind the placeholder until `reveal`.
+    let provenance_hash: Option<String> = get_optional_named_arg("provenance_hash");
+    let placeholder_meta: Option<Meta> = get_optional_named_arg("placeholder_meta");
+    let pending_reveal = match (provenance_hash, placeholder_meta) {
+        (Some(provenance_hash), Some(placeholder)) => Some((provenance_hash, placeholder)),
+        (None, None) => None,
+        _ => runtime::revert(ApiError::MissingArgument),
+    };
+    let lock_collection_meta: bool =
+        get_optional_named_arg("lock_collection_meta").unwrap_or_default();
+
+    // Prepare constructor args
+    let constructor_args = runtime_args! {
+        "name" => name,
+        "symbol" => symbol,
+        "meta" => meta,
+        "burn_policy" => burn_policy,
+        "recipient_policy" => recipient_policy,
+        "forbid_remint" => forbid_remint,
+        "meta_schema" => meta_schema,
+        "keep_meta_history" => keep_meta_history,
+        "base_uri" => base_uri,
+        "pending_reveal" => pending_reveal,
+        "lock_collection_meta" => lock_collection_meta
+    };
+
+    // Keep the package access URef so that new versions can be added later.
+    let (contract_hash, _) = storage::new_contract(
+        get_entry_points::<Id>(),
+        None,
+        Some(format!("{}_contract_package_hash", contract_name)),
+        Some(format!("{}_package_access_token", contract_name)),
+    );
+
+    let package_hash: ContractPackageHash = ContractPackageHash::new(
+        runtime::get_key(&format!("{}_contract_package_hash", contract_name))
+            .unwrap_or_revert()
+            .into_hash()
+            .unwrap_or_revert(),
+    );
+
+    let constructor_access: URef =
+        storage::create_contract_user_group(package_hash, "constructor", 1, Default::default())
+            .unwrap_or_revert()
+            .pop()
+            .unwrap_or_revert();
+
+    // Upgrades provision a uref for this group only while `migrate` runs.
+    storage::create_contract_user_group(package_hash, "migrate", 0, Default::default())
+        .unwrap_or_revert();
+
+    let _: () = runtime::call_contract(contract_hash, "constructor", constructor_args);
+
+    let mut urefs = BTreeSet::new();
+    urefs.insert(constructor_access);
+    storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
+        .unwrap_or_revert();
+
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash_wrapped", contract_name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
+
+/// Collects the optional `meta_*` install args into a schema. Without any of
+/// them token metadata is not validated.
+fn meta_schema_arg() -> Option<MetaSchema> {
+    let required_keys: Option<Vec<String>> = get_optional_named_arg("meta_required_keys");
+    let allowed_keys: Option<Vec<String>> = get_optional_named_arg("meta_allowed_keys");
+    let max_key_length: Option<u32> = get_optional_named_arg("meta_max_key_length");
+    let max_value_length: Option<u32> = get_optional_named_arg("meta_max_value_length");
+    let max_total_size: Option<u32> = get_optional_named_arg("meta_max_total_size");
+    if required_keys.is_none()
+        && allowed_keys.is_none()
+        && max_key_length.is_none()
+        && max_value_length.is_none()
+        && max_total_size.is_none()
+    {
+        return None;
+    }
+    Some(MetaSchema {
+        required_keys: required_keys.unwrap_or_default(),
+        allowed_keys,
+        max_key_length,
+        max_value_length,
+        max_total_size,
+    })
+}
+
+/// Adds a version to a package installed by `install`, which keeps the
+/// package access URef and sets up the `migrate` group. Packages installed
+/// before upgrades were supported kept neither, so no version can be added
+/// to them: those collections have to be installed anew.
+fn upgrade<Id: TokenIdentifier>(contract_name: String, package_key: Key) {
+    if runtime::get_key(&format!("{}_package_access_token", contract_name)).is_none() {
+        runtime::revert(ApiError::PermissionDenied);
+    }
+    let package_hash = ContractPackageHash::new(package_key.into_hash().unwrap_or_revert());
+    let previous_contract_hash = ContractHash::new(
+        runtime::get_key(&format!("{}_contract_hash", contract_name))
+            .unwrap_or_revert()
+            .into_hash()
+            .unwrap_or_revert(),
+    );
+
+    // Named keys, including every dictionary, carry over from the previous version.
+    let (contract_hash, _) =
+        storage::add_contract_version(package_hash, get_entry_points::<Id>(), NamedKeys::new());
+    storage::disable_contract_version(package_hash, previous_contract_hash).unwrap_or_revert();
+
+    let migrate_access: URef =
+        storage::provision_contract_user_group_uref(package_hash, "migrate").unwrap_or_revert();
+
+    let _: () = runtime::call_contract(contract_hash, "migrate", runtime_args! {});
+
+    let mut urefs = BTreeSet::new();
+    urefs.insert(migrate_access);
+    storage::remove_contract_user_group_urefs(package_hash, "migrate", urefs).unwrap_or_revert();
+
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash_wrapped", contract_name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
+
+/// Entry points of a contract with tokens identified by `Id`.
+pub fn get_entry_points<Id: TokenIdentifier>() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "constructor",
+        vec![
+            Parameter::new("name", String::cl_type()),
+            Parameter::new("symbol", String::cl_type()),
+            Parameter::new("meta", Meta::cl_type()),
+            Parameter::new("burn_policy", u8::cl_type()),
+            Parameter::new("recipient_policy", u8::cl_type()),
+            Parameter::new("forbid_remint", bool::cl_type()),
+            Parameter::new("meta_schema", Option::<MetaSchema>::cl_type()),
+            Parameter::new("keep_meta_history", bool::cl_type()),
+            Parameter::new("base_uri", String::cl_type()),
+            Parameter::new("pending_reveal", Option::<(String, Meta)>::cl_type()),
+            Parameter::new("lock_collection_meta", bool::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("constructor")]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("migrate")]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate_token_keys",
+        vec![Parameter::new(
+            "token_ids",
+            CLType::List(Box::new(Id::cl_type())),
+        )],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "finish_token_key_migration",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate_account_keys",
+        vec![Parameter::new(
+            "accounts",
+            CLType::List(Box::new(Key::cl_type())),
+        )],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "finish_account_key_migration",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "name",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "symbol",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "meta",
+        vec![],
+        Meta::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_name",
+        vec![Parameter::new("name", String::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_symbol",
+        vec![Parameter::new("symbol", String::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_meta",
+        vec![Parameter::new("meta", Meta::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "update_meta_keys",
+        vec![
+            Parameter::new("set", Meta::cl_type()),
+            Parameter::new("remove", Vec::<String>::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_supply",
+        vec![],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of",
+        vec![Parameter::new("owner", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "owner_of",
+        vec![Parameter::new("token_id", Id::cl_type())],
+        CLType::Option(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "token_meta",
+        vec![Parameter::new("token_id", Id::cl_type())],
+        Meta::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "token_meta_version_count",
+        vec![Parameter::new("token_id", Id::cl_type())],
+        CLType::U32,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "token_meta_at",
+        vec![
+            Parameter::new("token_id", Id::cl_type()),
+            Parameter::new("version", CLType::U32),
+        ],
+        CLType::Option(Box::new(Meta::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "update_token_meta",
+        vec![
+            Parameter::new("token_id", Id::cl_type()),
+            Parameter::new("token_meta", Meta::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "update_token_meta_and_checksum",
+        vec![
+            Parameter::new("token_id", Id::cl_type()),
+            Parameter::new("token_meta", Meta::cl_type()),
+            Parameter::new("token_checksum", Checksum::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "token_checksum",
+        vec![Parameter::new("token_id", Id::cl_type())],
+        CLType::Option(Box::new(Checksum::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "update_token_meta_keys",
+        vec![
+            Parameter::new("token_id", Id::cl_type()),
+            Parameter::new("set", Meta::cl_type()),
+            Parameter::new("remove", Vec::<String>::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_meta_frozen",
+        vec![Parameter::new("token_id", Id::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "freeze_token_meta",
+        vec![Parameter::new(
+            "token_ids",
+            CLType::List(Box::new(Id::cl_type())),
+        )],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "freeze_all_meta",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "base_uri",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_base_uri",
+        vec![Parameter::new("base_uri", String::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "token_uri",
+        vec![Parameter::new("token_id", Id::cl_type())],
+        CLType::Option(Box::new(String::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_token_uri",
+        vec![
+            Parameter::new("token_id", Id::cl_type()),
+            Parameter::new("uri", CLType::Option(Box::new(String::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "provenance_hash",
+        vec![],
+        CLType::Option(Box::new(String::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_revealed",
+        vec![],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "reveal",
+        vec![
+            Parameter::new("base_uri", String::cl_type()),
+            Parameter::new("offset_seed", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "reveal_token_meta",
+        vec![
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+            Parameter::new("token_metas", CLType::List(Box::new(Meta::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+            Parameter::new("token_metas", CLType::List(Box::new(Meta::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint_with_checksums",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+            Parameter::new("token_metas", CLType::List(Box::new(Meta::cl_type()))),
+            Parameter::new(
+                "token_checksums",
+                CLType::List(Box::new(Checksum::cl_type())),
+            ),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint_copies",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+            Parameter::new("token_meta", Meta::cl_type()),
+            Parameter::new("count", CLType::U32),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "burn",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "burn_as_admin",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "burn_policy",
+        vec![],
+        u8::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "recipient_policy",
+        vec![],
+        u8::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "burned_supply",
+        vec![],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_burned",
+        vec![Parameter::new("token_id", Id::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_from",
+        vec![
+            Parameter::new("sender", Key::cl_type()),
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "approve",
+        vec![
+            Parameter::new("spender", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "revoke",
+        vec![Parameter::new(
+            "token_ids",
+            CLType::List(Box::new(Id::cl_type())),
+        )],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_approved",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("token_id", Id::cl_type()),
+        ],
+        CLType::Option(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_token_by_index",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("index", U256::cl_type()),
+        ],
+        CLType::Option(Box::new(Id::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "parent_of",
+        vec![Parameter::new("token_id", Id::cl_type())],
+        CLType::Option(Box::new(Id::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "children_of",
+        vec![Parameter::new("token_id", Id::cl_type())],
+        CLType::List(Box::new(Id::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "root_owner_of",
+        vec![Parameter::new("token_id", Id::cl_type())],
+        CLType::Option(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "attach_child",
+        vec![
+            Parameter::new("parent_id", Id::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "detach_child",
+        vec![
+            Parameter::new("parent_id", Id::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "max_mint_per_wallet",
+        vec![],
+        CLType::Option(Box::new(CLType::U32)),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_max_mint_per_wallet",
+        vec![Parameter::new(
+            "limit",
+            CLType::Option(Box::new(CLType::U32)),
+        )],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "wallet_minted",
+        vec![Parameter::new("account", Key::cl_type())],
+        CLType::U32,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "purchase_mint",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+            Parameter::new("purse", URef::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "sale_item",
+        vec![Parameter::new("token_id", Id::cl_type())],
+        CLType::Option(Box::new(SaleItem::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "list_sale_items",
+        vec![
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+            Parameter::new("token_metas", CLType::List(Box::new(Meta::cl_type()))),
+            Parameter::new("tier", CLType::Option(Box::new(String::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "unlist_sale_items",
+        vec![Parameter::new(
+            "token_ids",
+            CLType::List(Box::new(Id::cl_type())),
+        )],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint_price",
+        vec![],
+        CLType::Option(Box::new(U512::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_mint_price",
+        vec![Parameter::new(
+            "price",
+            CLType::Option(Box::new(U512::cl_type())),
+        )],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "tier_price",
+        vec![Parameter::new("tier", String::cl_type())],
+        CLType::Option(Box::new(U512::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_tier_price",
+        vec![
+            Parameter::new("tier", String::cl_type()),
+            Parameter::new("price", CLType::Option(Box::new(U512::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_treasury",
+        vec![Parameter::new("treasury", AccountHash::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "withdraw",
+        vec![Parameter::new("amount", U512::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_mint_phase",
+        vec![
+            Parameter::new("phase", String::cl_type()),
+            Parameter::new("start_time", u64::cl_type()),
+            Parameter::new("end_time", u64::cl_type()),
+            Parameter::new("wallet_limit", u32::cl_type()),
+            Parameter::new("price", U512::cl_type()),
+            Parameter::new(
+                "merkle_root",
+                CLType::Option(Box::new(<[u8; 32]>::cl_type())),
+            ),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "remove_mint_phase",
+        vec![Parameter::new("phase", String::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_allowlist",
+        vec![
+            Parameter::new("phase", String::cl_type()),
+            Parameter::new("accounts", CLType::List(Box::new(Key::cl_type()))),
+            Parameter::new("allowed", bool::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_allowlisted",
+        vec![
+            Parameter::new("phase", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "phase_minted",
+        vec![
+            Parameter::new("phase", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        u32::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "phase_mint",
+        vec![
+            Parameter::new("phase", String::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(Id::cl_type()))),
+            Parameter::new("proof", CLType::List(Box::new(<[u8; 32]>::cl_type()))),
+            Parameter::new("purse", CLType::Option(Box::new(URef::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "add_admin",
+        vec![Parameter::new("address", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "disable_admin",
+        vec![Parameter::new("address", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
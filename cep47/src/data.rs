@@ -4,13 +4,28 @@ use alloc::{
     vec::Vec,
 };
 use casper_contract::{
-    contract_api::{runtime::get_call_stack, storage},
+    contract_api::{
+        runtime::{self, get_call_stack},
+        storage,
+    },
     unwrap_or_revert::UnwrapOrRevert,
 };
-use casper_types::{system::CallStackElement, ContractPackageHash, Key, URef, U256};
-use contract_utils::{get_key, key_and_value_to_str, key_to_str, set_key, Dict};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    system::CallStackElement,
+    CLTyped, ContractPackageHash, Key, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, U256, U512,
+};
+use contract_utils::{
+    get_key, key_and_value_to_str, set_key, Blake2bKey, Dict, KeyEncoder, KeyStr, StringKey,
+    TypedDict,
+};
+use core::convert::TryFrom;
 
-use crate::{event::CEP47Event, Meta, TokenId};
+use crate::{
+    event::CEP47Event, BurnPolicy, Checksum, Meta, MetaSchema, MintPhase, RecipientPolicy,
+    SaleItem, TokenId, TokenIdentifier,
+};
 
 const BALANCES_DICT: &str = "balances";
 pub const ALLOWANCES_DICT: &str = "allowances";
@@ -18,21 +33,131 @@ const METADATA_DICT: &str = "metadata";
 const OWNERS_DICT: &str = "owners";
 const OWNED_TOKENS_BY_INDEX_DICT: &str = "owned_tokens_by_index";
 const OWNED_INDEXES_BY_TOKEN_DICT: &str = "owned_indexes_by_token";
+const PARENTS_DICT: &str = "parents";
+const CHILDREN_DICT: &str = "children";
+const TIER_PRICES_DICT: &str = "tier_prices";
+const SALE_ITEMS_DICT: &str = "sale_items";
+const MINT_PHASES_DICT: &str = "mint_phases";
+const PHASE_ALLOWLIST_DICT: &str = "phase_allowlist";
+const PHASE_MINTS_DICT: &str = "phase_mints";
+const WALLET_MINTS_DICT: &str = "wallet_mints";
+const BURNED_TOKENS_DICT: &str = "burned_tokens";
+const FROZEN_METADATA_DICT: &str = "frozen_metadata";
+const METADATA_VERSIONS_DICT: &str = "metadata_versions";
+const METADATA_HISTORY_DICT: &str = "metadata_history";
+const TOKEN_URIS_DICT: &str = "token_uris";
+const CHECKSUMS_DICT: &str = "checksums";
+const META_COMMITMENTS_DICT: &str = "meta_commitments";
 const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
 
 pub const NAME: &str = "name";
 pub const META: &str = "meta";
 pub const SYMBOL: &str = "symbol";
 pub const TOTAL_SUPPLY: &str = "total_supply";
+pub const STORAGE_VERSION: &str = "storage_version";
+pub const PURSE: &str = "purse";
+pub const MINT_PRICE: &str = "mint_price";
+pub const TREASURY: &str = "treasury";
+pub const MAX_MINT_PER_WALLET: &str = "max_mint_per_wallet";
+pub const BURN_POLICY: &str = "burn_policy";
+pub const RECIPIENT_POLICY: &str = "recipient_policy";
+pub const BURNED_SUPPLY: &str = "burned_supply";
+pub const FORBID_REMINT: &str = "forbid_remint";
+pub const ALL_META_FROZEN: &str = "all_meta_frozen";
+pub const META_SCHEMA: &str = "meta_schema";
+pub const KEEP_META_HISTORY: &str = "keep_meta_history";
+pub const BASE_URI: &str = "base_uri";
+pub const PROVENANCE_HASH: &str = "provenance_hash";
+pub const PLACEHOLDER_META: &str = "placeholder_meta";
+pub const REVEAL_OFFSET: &str = "reveal_offset";
+pub const REVEAL_SUPPLY: &str = "reveal_supply";
+pub const COLLECTION_META_LOCKED: &str = "collection_meta_locked";
+pub const LEGACY_TOKEN_KEYS: &str = "legacy_token_keys";
 
-pub struct Owners {
-    dict: Dict,
+/// Layout version written by this code. Bump it together with a new arm in
+/// `CEP47::migrate_from` whenever an upgrade needs to touch existing storage.
+pub const CURRENT_STORAGE_VERSION: u32 = 16;
+
+/// Item key of a token id, see `TokenIdentifier::item_key`.
+pub struct TokenIdKey;
+
+impl<Id: TokenIdentifier> KeyEncoder<Id> for TokenIdKey {
+    fn encode(key: &Id) -> String {
+        key.item_key()
+    }
+}
+
+/// A dictionary keyed by token id. Collections installed before fixed length
+/// keys may still hold items under the decimal id: those are read as a
+/// fallback and dropped when the item is written, until `migrate` moved them
+/// all and `legacy_token_keys` is switched off. Ids whose decimal form is
+/// too long for an item key can't have been stored that way and are skipped.
+pub struct TokenDict<Id, V> {
+    dict: TypedDict<Id, V, TokenIdKey>,
+    legacy: Option<TypedDict<Id, V, StringKey>>,
+}
+
+impl<Id: TokenIdentifier, V: CLTyped + ToBytes + FromBytes> TokenDict<Id, V> {
+    pub fn instance(name: &str) -> TokenDict<Id, V> {
+        let uref = *runtime::get_key(name)
+            .unwrap_or_revert()
+            .as_uref()
+            .unwrap_or_revert();
+        TokenDict {
+            dict: TypedDict::at(uref),
+            legacy: legacy_token_keys().then(|| TypedDict::at(uref)),
+        }
+    }
+
+    pub fn get(&self, key: &Id) -> Option<V> {
+        self.dict
+            .get(key)
+            .or_else(|| self.legacy(key).and_then(|legacy| legacy.get(key)))
+    }
+
+    pub fn set(&self, key: &Id, value: V) {
+        self.dict.set(key, value);
+        self.remove_legacy(key);
+    }
+
+    pub fn remove(&self, key: &Id) {
+        self.dict.remove(key);
+        self.remove_legacy(key);
+    }
+
+    /// Moves the item of `key` from its decimal to its fixed length key.
+    pub fn migrate(&self, key: &Id) {
+        if let Some(legacy) = self.legacy(key) {
+            if let Some(value) = legacy.get(key) {
+                self.dict.set(key, value);
+                legacy.remove(key);
+            }
+        }
+    }
+
+    fn remove_legacy(&self, key: &Id) {
+        if let Some(legacy) = self.legacy(key) {
+            if legacy.get(key).is_some() {
+                legacy.remove(key);
+            }
+        }
+    }
+
+    fn legacy(&self, key: &Id) -> Option<&TypedDict<Id, V, StringKey>> {
+        self.legacy
+            .as_ref()
+            .filter(|_| key.to_string().len() <= DICTIONARY_ITEM_KEY_MAX_LENGTH)
+    }
+}
+
+pub struct Owners<Id = TokenId> {
+    dict: TokenDict<Id, Key>,
 }
 
-impl Owners {
-    pub fn instance() -> Owners {
+impl<Id: TokenIdentifier> Owners<Id> {
+    pub fn instance() -> Owners<Id> {
         Owners {
-            dict: Dict::instance(OWNERS_DICT),
+            dict: TokenDict::instance(OWNERS_DICT),
         }
     }
 
@@ -40,27 +165,31 @@ impl Owners {
         Dict::init(OWNERS_DICT)
     }
 
-    pub fn get(&self, key: &TokenId) -> Option<Key> {
-        self.dict.get(&key.to_string())
+    pub fn get(&self, key: &Id) -> Option<Key> {
+        self.dict.get(key)
     }
 
-    pub fn set(&self, key: &TokenId, value: Key) {
-        self.dict.set(&key.to_string(), value);
+    pub fn set(&self, key: &Id, value: Key) {
+        self.dict.set(key, value);
     }
 
-    pub fn remove(&self, key: &TokenId) {
-        self.dict.remove::<Key>(&key.to_string());
+    pub fn remove(&self, key: &Id) {
+        self.dict.remove(key);
+    }
+
+    pub fn migrate(&self, key: &Id) {
+        self.dict.migrate(key);
     }
 }
 
-pub struct Metadata {
-    dict: Dict,
+pub struct Metadata<Id = TokenId> {
+    dict: TokenDict<Id, Meta>,
 }
 
-impl Metadata {
-    pub fn instance() -> Metadata {
+impl<Id: TokenIdentifier> Metadata<Id> {
+    pub fn instance() -> Metadata<Id> {
         Metadata {
-            dict: Dict::instance(METADATA_DICT),
+            dict: TokenDict::instance(METADATA_DICT),
         }
     }
 
@@ -68,31 +197,139 @@ impl Metadata {
         Dict::init(METADATA_DICT)
     }
 
-    pub fn get(&self, key: &TokenId) -> Option<Meta> {
-        self.dict.get(&key.to_string())
+    pub fn get(&self, key: &Id) -> Option<Meta> {
+        self.dict.get(key)
+    }
+
+    pub fn set(&self, key: &Id, value: Meta) {
+        self.dict.set(key, value);
+    }
+
+    pub fn remove(&self, key: &Id) {
+        self.dict.remove(key);
+    }
+
+    pub fn migrate(&self, key: &Id) {
+        self.dict.migrate(key);
+    }
+}
+
+/// Digest of the metadata each token was minted with while the collection
+/// was unrevealed, in place of the metadata itself.
+pub struct MetaCommitments<Id = TokenId> {
+    dict: TokenDict<Id, String>,
+}
+
+impl<Id: TokenIdentifier> MetaCommitments<Id> {
+    pub fn instance() -> MetaCommitments<Id> {
+        MetaCommitments {
+            dict: TokenDict::instance(META_COMMITMENTS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(META_COMMITMENTS_DICT)
+    }
+
+    pub fn get(&self, key: &Id) -> Option<String> {
+        self.dict.get(key)
+    }
+
+    pub fn set(&self, key: &Id, value: String) {
+        self.dict.set(key, value);
+    }
+
+    pub fn remove(&self, key: &Id) {
+        self.dict.remove(key);
+    }
+}
+
+pub struct Parents<Id = TokenId> {
+    dict: TokenDict<Id, Id>,
+}
+
+impl<Id: TokenIdentifier> Parents<Id> {
+    pub fn instance() -> Parents<Id> {
+        Parents {
+            dict: TokenDict::instance(PARENTS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(PARENTS_DICT)
+    }
+
+    pub fn get(&self, key: &Id) -> Option<Id> {
+        self.dict.get(key)
+    }
+
+    pub fn set(&self, key: &Id, value: Id) {
+        self.dict.set(key, value);
+    }
+
+    pub fn remove(&self, key: &Id) {
+        self.dict.remove(key);
+    }
+
+    pub fn migrate(&self, key: &Id) {
+        self.dict.migrate(key);
+    }
+}
+
+pub struct Children<Id = TokenId> {
+    dict: TokenDict<Id, Vec<Id>>,
+}
+
+impl<Id: TokenIdentifier> Children<Id> {
+    pub fn instance() -> Children<Id> {
+        Children {
+            dict: TokenDict::instance(CHILDREN_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(CHILDREN_DICT)
+    }
+
+    pub fn get(&self, parent: &Id) -> Vec<Id> {
+        self.dict.get(parent).unwrap_or_default()
     }
 
-    pub fn set(&self, key: &TokenId, value: Meta) {
-        self.dict.set(&key.to_string(), value);
+    pub fn add(&self, parent: &Id, child: &Id) {
+        let mut children = self.get(parent);
+        children.push(child.clone());
+        self.dict.set(parent, children);
     }
 
-    pub fn remove(&self, key: &TokenId) {
-        self.dict.remove::<Meta>(&key.to_string());
+    pub fn remove(&self, parent: &Id, child: &Id) {
+        let mut children = self.get(parent);
+        children.retain(|token_id| token_id != child);
+        if children.is_empty() {
+            self.dict.remove(parent);
+        } else {
+            self.dict.set(parent, children);
+        }
+    }
+
+    pub fn migrate(&self, key: &Id) {
+        self.dict.migrate(key);
     }
 }
 
-pub struct OwnedTokens {
-    tokens_dict: Dict,
-    indexes_dict: Dict,
-    balances_dict: Dict,
+/// Both index dictionaries hash `(owner, index)` and `(owner, token_id)`, so
+/// their item keys match the ones written before `TypedDict`.
+pub struct OwnedTokens<Id = TokenId> {
+    tokens_dict: TypedDict<(Key, U256), Id, Blake2bKey>,
+    indexes_dict: TypedDict<(Key, Id), U256, Blake2bKey>,
+    balances_dict: TypedDict<Key, U256, KeyStr>,
 }
 
-impl OwnedTokens {
-    pub fn instance() -> OwnedTokens {
+impl<Id: TokenIdentifier> OwnedTokens<Id> {
+    pub fn instance() -> OwnedTokens<Id> {
         OwnedTokens {
-            tokens_dict: Dict::instance(OWNED_TOKENS_BY_INDEX_DICT),
-            indexes_dict: Dict::instance(OWNED_INDEXES_BY_TOKEN_DICT),
-            balances_dict: Dict::instance(BALANCES_DICT),
+            tokens_dict: TypedDict::instance(OWNED_TOKENS_BY_INDEX_DICT),
+            indexes_dict: TypedDict::instance(OWNED_INDEXES_BY_TOKEN_DICT),
+            balances_dict: TypedDict::instance(BALANCES_DICT),
         }
     }
 
@@ -102,71 +339,67 @@ impl OwnedTokens {
         Dict::init(BALANCES_DICT);
     }
 
-    pub fn get_token_by_index(&self, owner: &Key, index: &U256) -> Option<TokenId> {
-        self.tokens_dict.get(&key_and_value_to_str(owner, index))
+    pub fn get_token_by_index(&self, owner: &Key, index: &U256) -> Option<Id> {
+        self.tokens_dict.get(&(*owner, *index))
     }
 
-    pub fn get_index_by_token(&self, owner: &Key, value: &TokenId) -> Option<U256> {
-        self.indexes_dict.get(&key_and_value_to_str(owner, value))
+    pub fn get_index_by_token(&self, owner: &Key, value: &Id) -> Option<U256> {
+        self.indexes_dict.get(&(*owner, value.clone()))
     }
 
     pub fn get_balances(&self, owner: &Key) -> U256 {
-        self.balances_dict
-            .get(&key_to_str(owner))
-            .unwrap_or_default()
+        self.balances_dict.get(owner).unwrap_or_default()
     }
 
     pub fn set_balances(&self, owner: &Key, value: U256) {
-        self.balances_dict.set(&key_to_str(owner), value);
+        self.balances_dict.set(owner, value);
+    }
+
+    /// Moves the balance of `owner` off its legacy item key.
+    pub fn migrate_balance(&self, owner: &Key) {
+        self.balances_dict.migrate(owner);
     }
 
-    pub fn set_token(&self, owner: &Key, value: &TokenId) {
+    pub fn set_token(&self, owner: &Key, value: &Id) {
         let length = self.get_balances(owner);
-        self.indexes_dict
-            .set(&key_and_value_to_str(owner, value), length);
-        self.tokens_dict
-            .set(&key_and_value_to_str(owner, &length), *value);
+        self.indexes_dict.set(&(*owner, value.clone()), length);
+        self.tokens_dict.set(&(*owner, length), value.clone());
         self.set_balances(owner, length + 1);
     }
 
-    pub fn remove_token(&self, owner: &Key, value: &TokenId) {
+    pub fn remove_token(&self, owner: &Key, value: &Id) {
         let length = self.get_balances(owner);
         let index = self.get_index_by_token(owner, value).unwrap_or_revert();
         match length.cmp(&(index + 1)) {
             core::cmp::Ordering::Equal => {
-                self.tokens_dict
-                    .remove::<TokenId>(&key_and_value_to_str(owner, &(length - 1)));
+                self.tokens_dict.remove(&(*owner, length - 1));
                 self.set_balances(owner, length - 1);
             }
             core::cmp::Ordering::Greater => {
-                let last = self.get_token_by_index(owner, &(length - 1));
-                self.indexes_dict.set(
-                    &key_and_value_to_str(owner, &last.unwrap_or_revert()),
-                    index,
-                );
-                self.tokens_dict.set(
-                    &key_and_value_to_str(owner, &index),
-                    last.unwrap_or_revert(),
-                );
-                self.tokens_dict
-                    .remove::<TokenId>(&key_and_value_to_str(owner, &(length - 1)));
+                let last = self
+                    .get_token_by_index(owner, &(length - 1))
+                    .unwrap_or_revert();
+                self.indexes_dict.set(&(*owner, last.clone()), index);
+                self.tokens_dict.set(&(*owner, index), last);
+                self.tokens_dict.remove(&(*owner, length - 1));
                 self.set_balances(owner, length - 1);
             }
             core::cmp::Ordering::Less => {}
         }
-        self.indexes_dict
-            .remove::<U256>(&key_and_value_to_str(owner, value));
+        self.indexes_dict.remove(&(*owner, value.clone()));
     }
 }
 
+/// Keyed by the hash of `(owner, token_id.to_string())`, as written before
+/// `TypedDict`.
 pub struct Allowances {
-    dict: Dict,
+    dict: TypedDict<(Key, String), Key, Blake2bKey>,
 }
 
 impl Allowances {
     pub fn instance() -> Allowances {
         Allowances {
-            dict: Dict::instance(ALLOWANCES_DICT),
+            dict: TypedDict::instance(ALLOWANCES_DICT),
         }
     }
 
@@ -174,25 +407,433 @@ impl Allowances {
         Dict::init(ALLOWANCES_DICT)
     }
 
-    pub fn get(&self, owner: &Key, token_id: &TokenId) -> Option<Key> {
-        self.dict.get(&key_and_value_to_str::<String>(
-            owner,
-            &token_id.to_string(),
-        ))
+    pub fn get<Id: TokenIdentifier>(&self, owner: &Key, token_id: &Id) -> Option<Key> {
+        self.dict.get(&(*owner, token_id.to_string()))
+    }
+
+    pub fn set<Id: TokenIdentifier>(&self, owner: &Key, token_id: &Id, value: Key) {
+        self.dict.set(&(*owner, token_id.to_string()), value);
+    }
+
+    pub fn remove<Id: TokenIdentifier>(&self, owner: &Key, token_id: &Id) {
+        self.dict.remove(&(*owner, token_id.to_string()));
+    }
+}
+
+pub struct TierPrices {
+    dict: Dict,
+}
+
+impl TierPrices {
+    pub fn instance() -> TierPrices {
+        TierPrices {
+            dict: Dict::instance(TIER_PRICES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(TIER_PRICES_DICT)
+    }
+
+    pub fn get(&self, tier: &str) -> Option<U512> {
+        self.dict.get(tier)
+    }
+
+    pub fn set(&self, tier: &str, value: U512) {
+        self.dict.set(tier, value);
+    }
+
+    pub fn remove(&self, tier: &str) {
+        self.dict.remove::<U512>(tier);
+    }
+}
+
+/// Token ids admins listed for `purchase_mint` and `phase_mint`.
+pub struct SaleItems<Id = TokenId> {
+    dict: TokenDict<Id, SaleItem>,
+}
+
+impl<Id: TokenIdentifier> SaleItems<Id> {
+    pub fn instance() -> SaleItems<Id> {
+        SaleItems {
+            dict: TokenDict::instance(SALE_ITEMS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(SALE_ITEMS_DICT)
+    }
+
+    pub fn get(&self, key: &Id) -> Option<SaleItem> {
+        self.dict.get(key)
+    }
+
+    pub fn set(&self, key: &Id, value: SaleItem) {
+        self.dict.set(key, value);
+    }
+
+    pub fn remove(&self, key: &Id) {
+        self.dict.remove(key);
+    }
+}
+
+pub struct MintPhases {
+    dict: Dict,
+}
+
+impl MintPhases {
+    pub fn instance() -> MintPhases {
+        MintPhases {
+            dict: Dict::instance(MINT_PHASES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(MINT_PHASES_DICT)
+    }
+
+    pub fn get(&self, phase: &str) -> Option<MintPhase> {
+        self.dict.get(phase)
+    }
+
+    pub fn set(&self, phase: &str, value: MintPhase) {
+        self.dict.set(phase, value);
+    }
+
+    pub fn remove(&self, phase: &str) {
+        self.dict.remove::<MintPhase>(phase);
+    }
+}
+
+pub struct PhaseAllowlist {
+    dict: Dict,
+}
+
+impl PhaseAllowlist {
+    pub fn instance() -> PhaseAllowlist {
+        PhaseAllowlist {
+            dict: Dict::instance(PHASE_ALLOWLIST_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(PHASE_ALLOWLIST_DICT)
+    }
+
+    pub fn is_allowed(&self, phase: &str, account: &Key) -> bool {
+        self.dict
+            .get(&key_and_value_to_str::<String>(account, &phase.to_string()))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, phase: &str, account: &Key, allowed: bool) {
+        let key = key_and_value_to_str::<String>(account, &phase.to_string());
+        if allowed {
+            self.dict.set(&key, true);
+        } else {
+            self.dict.remove::<bool>(&key);
+        }
+    }
+}
+
+pub struct PhaseMints {
+    dict: Dict,
+}
+
+impl PhaseMints {
+    pub fn instance() -> PhaseMints {
+        PhaseMints {
+            dict: Dict::instance(PHASE_MINTS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(PHASE_MINTS_DICT)
+    }
+
+    pub fn get(&self, phase: &str, account: &Key) -> u32 {
+        self.dict
+            .get(&key_and_value_to_str::<String>(account, &phase.to_string()))
+            .unwrap_or_default()
     }
 
-    pub fn set(&self, owner: &Key, token_id: &TokenId, value: Key) {
+    pub fn set(&self, phase: &str, account: &Key, value: u32) {
         self.dict.set(
-            &key_and_value_to_str::<String>(owner, &token_id.to_string()),
+            &key_and_value_to_str::<String>(account, &phase.to_string()),
             value,
         );
     }
+}
 
-    pub fn remove(&self, owner: &Key, token_id: &TokenId) {
-        self.dict.remove::<Key>(&key_and_value_to_str::<String>(
-            owner,
-            &token_id.to_string(),
-        ));
+/// Tokens minted per account, kept apart from balances, which drop on transfer.
+pub struct WalletMints {
+    dict: Dict,
+}
+
+impl WalletMints {
+    pub fn instance() -> WalletMints {
+        WalletMints {
+            dict: Dict::instance(WALLET_MINTS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(WALLET_MINTS_DICT)
+    }
+
+    pub fn get(&self, account: &Key) -> u32 {
+        self.dict.get_by_key(account).unwrap_or_default()
+    }
+
+    pub fn set(&self, account: &Key, value: u32) {
+        self.dict.set_by_key(account, value);
+    }
+
+    pub fn migrate(&self, account: &Key) {
+        self.dict.migrate_by_key::<u32>(account);
+    }
+}
+
+/// Tombstones of burned tokens, holding the owner the token was burned from.
+pub struct BurnedTokens<Id = TokenId> {
+    dict: TokenDict<Id, Key>,
+}
+
+impl<Id: TokenIdentifier> BurnedTokens<Id> {
+    pub fn instance() -> BurnedTokens<Id> {
+        BurnedTokens {
+            dict: TokenDict::instance(BURNED_TOKENS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(BURNED_TOKENS_DICT)
+    }
+
+    pub fn get(&self, key: &Id) -> Option<Key> {
+        self.dict.get(key)
+    }
+
+    pub fn set(&self, key: &Id, value: Key) {
+        self.dict.set(key, value);
+    }
+
+    pub fn remove(&self, key: &Id) {
+        self.dict.remove(key);
+    }
+
+    pub fn migrate(&self, key: &Id) {
+        self.dict.migrate(key);
+    }
+}
+
+/// Tokens whose metadata can no longer be updated.
+pub struct FrozenMetadata<Id = TokenId> {
+    dict: TokenDict<Id, bool>,
+}
+
+impl<Id: TokenIdentifier> FrozenMetadata<Id> {
+    pub fn instance() -> FrozenMetadata<Id> {
+        FrozenMetadata {
+            dict: TokenDict::instance(FROZEN_METADATA_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(FROZEN_METADATA_DICT)
+    }
+
+    pub fn is_frozen(&self, key: &Id) -> bool {
+        self.dict.get(key).unwrap_or_default()
+    }
+
+    pub fn freeze(&self, key: &Id) {
+        self.dict.set(key, true);
+    }
+
+    pub fn unfreeze(&self, key: &Id) {
+        if self.is_frozen(key) {
+            self.dict.remove(key);
+        }
+    }
+
+    pub fn migrate(&self, key: &Id) {
+        self.dict.migrate(key);
+    }
+}
+
+/// Current metadata version of each token, `0` for the metadata it was minted
+/// with.
+pub struct MetadataVersions<Id = TokenId> {
+    dict: TokenDict<Id, u32>,
+}
+
+impl<Id: TokenIdentifier> MetadataVersions<Id> {
+    pub fn instance() -> MetadataVersions<Id> {
+        MetadataVersions {
+            dict: TokenDict::instance(METADATA_VERSIONS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(METADATA_VERSIONS_DICT)
+    }
+
+    pub fn get(&self, key: &Id) -> u32 {
+        self.dict.get(key).unwrap_or_default()
+    }
+
+    pub fn set(&self, key: &Id, value: u32) {
+        self.dict.set(key, value);
+    }
+
+    pub fn remove(&self, key: &Id) {
+        self.dict.remove(key);
+    }
+
+    pub fn migrate(&self, key: &Id) {
+        self.dict.migrate(key);
+    }
+}
+
+/// Superseded metadata by token and version, kept while `keep_meta_history`
+/// is set.
+pub struct MetadataHistory {
+    dict: Dict,
+    legacy: bool,
+}
+
+impl MetadataHistory {
+    pub fn instance() -> MetadataHistory {
+        MetadataHistory {
+            dict: Dict::instance(METADATA_HISTORY_DICT),
+            legacy: legacy_token_keys(),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(METADATA_HISTORY_DICT)
+    }
+
+    pub fn get<Id: TokenIdentifier>(&self, token_id: &Id, version: u32) -> Option<Meta> {
+        self.dict.get(&history_key(token_id, version)).or_else(|| {
+            self.legacy_key(token_id, version)
+                .and_then(|legacy| self.dict.get(&legacy))
+        })
+    }
+
+    pub fn set<Id: TokenIdentifier>(&self, token_id: &Id, version: u32, value: Meta) {
+        self.dict.set(&history_key(token_id, version), value);
+    }
+
+    /// Drops the first `versions` entries of `token_id`.
+    pub fn clear<Id: TokenIdentifier>(&self, token_id: &Id, versions: u32) {
+        for version in 0..versions {
+            if self.get(token_id, version).is_some() {
+                self.dict.remove::<Meta>(&history_key(token_id, version));
+                if let Some(legacy) = self.legacy_key(token_id, version) {
+                    self.dict.remove::<Meta>(&legacy);
+                }
+            }
+        }
+    }
+
+    /// Moves the first `versions` entries of `token_id` from decimal to fixed
+    /// length keys.
+    pub fn migrate<Id: TokenIdentifier>(&self, token_id: &Id, versions: u32) {
+        for version in 0..versions {
+            if let Some(legacy) = self.legacy_key(token_id, version) {
+                if let Some(value) = self.dict.get::<Meta>(&legacy) {
+                    self.dict.set(&history_key(token_id, version), value);
+                    self.dict.remove::<Meta>(&legacy);
+                }
+            }
+        }
+    }
+
+    /// Numeric ids' entries written before fixed length keys are keyed by the
+    /// decimal id.
+    fn legacy_key<Id: TokenIdentifier>(&self, token_id: &Id, version: u32) -> Option<String> {
+        if !self.legacy {
+            return None;
+        }
+        token_id
+            .as_index()
+            .map(|index| format!("{}_{}", index, version))
+            .filter(|key| key.len() <= DICTIONARY_ITEM_KEY_MAX_LENGTH)
+    }
+}
+
+/// Hex blake2b digest of the token's item key and `version`, so history
+/// entries fit an item key however many versions a token has.
+fn history_key<Id: TokenIdentifier>(token_id: &Id, version: u32) -> String {
+    let bytes = (token_id.item_key(), version).to_bytes().unwrap_or_revert();
+    hex::encode(runtime::blake2b(bytes))
+}
+
+/// URIs that override the `base_uri` derived one for single tokens.
+pub struct TokenUris<Id = TokenId> {
+    dict: TokenDict<Id, String>,
+}
+
+impl<Id: TokenIdentifier> TokenUris<Id> {
+    pub fn instance() -> TokenUris<Id> {
+        TokenUris {
+            dict: TokenDict::instance(TOKEN_URIS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(TOKEN_URIS_DICT)
+    }
+
+    pub fn get(&self, key: &Id) -> Option<String> {
+        self.dict.get(key)
+    }
+
+    pub fn set(&self, key: &Id, value: String) {
+        self.dict.set(key, value);
+    }
+
+    pub fn remove(&self, key: &Id) {
+        self.dict.remove(key);
+    }
+
+    pub fn migrate(&self, key: &Id) {
+        self.dict.migrate(key);
+    }
+}
+
+/// Content checksums of tokens whose payload lives off chain.
+pub struct Checksums<Id = TokenId> {
+    dict: TokenDict<Id, Checksum>,
+}
+
+impl<Id: TokenIdentifier> Checksums<Id> {
+    pub fn instance() -> Checksums<Id> {
+        Checksums {
+            dict: TokenDict::instance(CHECKSUMS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(CHECKSUMS_DICT)
+    }
+
+    pub fn get(&self, key: &Id) -> Option<Checksum> {
+        self.dict.get(key)
+    }
+
+    pub fn set(&self, key: &Id, value: Checksum) {
+        self.dict.set(key, value);
+    }
+
+    pub fn remove(&self, key: &Id) {
+        self.dict.remove(key);
+    }
+
+    pub fn migrate(&self, key: &Id) {
+        self.dict.migrate(key);
     }
 }
 
@@ -228,6 +869,168 @@ pub fn set_total_supply(total_supply: U256) {
     set_key(TOTAL_SUPPLY, total_supply);
 }
 
+pub fn purse() -> URef {
+    *runtime::get_key(PURSE)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert()
+}
+
+pub fn set_purse(purse: URef) {
+    runtime::put_key(PURSE, purse.into());
+}
+
+pub fn mint_price() -> Option<U512> {
+    get_key(MINT_PRICE).unwrap_or_default()
+}
+
+pub fn set_mint_price(price: Option<U512>) {
+    set_key(MINT_PRICE, price);
+}
+
+pub fn treasury() -> AccountHash {
+    get_key(TREASURY).unwrap_or_revert()
+}
+
+pub fn set_treasury(treasury: AccountHash) {
+    set_key(TREASURY, treasury);
+}
+
+pub fn max_mint_per_wallet() -> Option<u32> {
+    get_key(MAX_MINT_PER_WALLET).unwrap_or_default()
+}
+
+pub fn set_max_mint_per_wallet(limit: Option<u32>) {
+    set_key(MAX_MINT_PER_WALLET, limit);
+}
+
+pub fn burn_policy() -> BurnPolicy {
+    // Collections installed before burn policies keep the original rules.
+    get_key::<u8>(BURN_POLICY)
+        .map(|policy| BurnPolicy::try_from(policy).unwrap_or_revert())
+        .unwrap_or(BurnPolicy::OwnerOrApproved)
+}
+
+pub fn set_burn_policy(policy: BurnPolicy) {
+    set_key(BURN_POLICY, policy as u8);
+}
+
+pub fn recipient_policy() -> RecipientPolicy {
+    // Collections installed before recipient policies accept contracts.
+    get_key::<u8>(RECIPIENT_POLICY)
+        .map(|policy| RecipientPolicy::try_from(policy).unwrap_or_revert())
+        .unwrap_or(RecipientPolicy::AccountsAndContracts)
+}
+
+pub fn set_recipient_policy(policy: RecipientPolicy) {
+    set_key(RECIPIENT_POLICY, policy as u8);
+}
+
+pub fn burned_supply() -> U256 {
+    get_key(BURNED_SUPPLY).unwrap_or_default()
+}
+
+pub fn set_burned_supply(burned_supply: U256) {
+    set_key(BURNED_SUPPLY, burned_supply);
+}
+
+pub fn forbid_remint() -> bool {
+    get_key(FORBID_REMINT).unwrap_or_default()
+}
+
+pub fn set_forbid_remint(forbid: bool) {
+    set_key(FORBID_REMINT, forbid);
+}
+
+pub fn all_meta_frozen() -> bool {
+    get_key(ALL_META_FROZEN).unwrap_or_default()
+}
+
+pub fn set_all_meta_frozen(frozen: bool) {
+    set_key(ALL_META_FROZEN, frozen);
+}
+
+pub fn meta_schema() -> Option<MetaSchema> {
+    get_key(META_SCHEMA).unwrap_or_default()
+}
+
+pub fn set_meta_schema(schema: Option<MetaSchema>) {
+    set_key(META_SCHEMA, schema);
+}
+
+pub fn keep_meta_history() -> bool {
+    get_key(KEEP_META_HISTORY).unwrap_or_default()
+}
+
+pub fn set_keep_meta_history(keep: bool) {
+    set_key(KEEP_META_HISTORY, keep);
+}
+
+pub fn base_uri() -> String {
+    get_key(BASE_URI).unwrap_or_default()
+}
+
+pub fn set_base_uri(base_uri: String) {
+    set_key(BASE_URI, base_uri);
+}
+
+pub fn provenance_hash() -> Option<String> {
+    get_key(PROVENANCE_HASH).unwrap_or_default()
+}
+
+pub fn set_provenance_hash(provenance_hash: Option<String>) {
+    set_key(PROVENANCE_HASH, provenance_hash);
+}
+
+pub fn placeholder_meta() -> Option<Meta> {
+    get_key(PLACEHOLDER_META).unwrap_or_default()
+}
+
+pub fn set_placeholder_meta(meta: Option<Meta>) {
+    set_key(PLACEHOLDER_META, meta);
+}
+
+pub fn reveal_offset() -> Option<U256> {
+    get_key(REVEAL_OFFSET).unwrap_or_default()
+}
+
+pub fn set_reveal_offset(offset: Option<U256>) {
+    set_key(REVEAL_OFFSET, offset);
+}
+
+pub fn reveal_supply() -> U256 {
+    get_key(REVEAL_SUPPLY).unwrap_or_default()
+}
+
+pub fn set_reveal_supply(supply: U256) {
+    set_key(REVEAL_SUPPLY, supply);
+}
+
+pub fn collection_meta_locked() -> bool {
+    get_key(COLLECTION_META_LOCKED).unwrap_or_default()
+}
+
+pub fn set_collection_meta_locked(locked: bool) {
+    set_key(COLLECTION_META_LOCKED, locked);
+}
+
+pub fn legacy_token_keys() -> bool {
+    get_key(LEGACY_TOKEN_KEYS).unwrap_or_default()
+}
+
+pub fn set_legacy_token_keys(legacy: bool) {
+    set_key(LEGACY_TOKEN_KEYS, legacy);
+}
+
+pub fn storage_version() -> u32 {
+    // Deployments installed before versioning was introduced have no key.
+    get_key(STORAGE_VERSION).unwrap_or(1)
+}
+
+pub fn set_storage_version(version: u32) {
+    set_key(STORAGE_VERSION, version);
+}
+
 pub fn contract_package_hash() -> ContractPackageHash {
     let call_stacks = get_call_stack();
     let last_entry = call_stacks.last().unwrap_or_revert();
@@ -241,7 +1044,7 @@ pub fn contract_package_hash() -> ContractPackageHash {
     package_hash.unwrap_or_revert()
 }
 
-pub fn emit(event: &CEP47Event) {
+pub fn emit<Id: TokenIdentifier>(event: &CEP47Event<Id>) {
     let mut events = Vec::new();
     let package = contract_package_hash();
     match event {
@@ -308,11 +1111,104 @@ pub fn emit(event: &CEP47Event) {
                 events.push(param);
             }
         }
-        CEP47Event::MetadataUpdate { token_id } => {
+        CEP47Event::Attach {
+            owner,
+            parent_id,
+            token_ids,
+        } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "cep47_attach_child".to_string());
+                param.insert("owner", owner.to_string());
+                param.insert("parent_id", parent_id.to_string());
+                param.insert("token_id", token_id.to_string());
+                events.push(param);
+            }
+        }
+        CEP47Event::Detach {
+            owner,
+            parent_id,
+            token_ids,
+        } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "cep47_detach_child".to_string());
+                param.insert("owner", owner.to_string());
+                param.insert("parent_id", parent_id.to_string());
+                param.insert("token_id", token_id.to_string());
+                events.push(param);
+            }
+        }
+        CEP47Event::MetadataUpdate {
+            token_id,
+            changed_keys,
+            version,
+        } => {
             let mut param = BTreeMap::new();
             param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
             param.insert("event_type", "cep47_metadata_update".to_string());
             param.insert("token_id", token_id.to_string());
+            param.insert("changed_keys", changed_keys.join(","));
+            param.insert("version", version.to_string());
+            events.push(param);
+        }
+        CEP47Event::PermanentMetadata { token_ids } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "cep47_permanent_metadata".to_string());
+                param.insert("token_id", token_id.to_string());
+                events.push(param);
+            }
+        }
+        CEP47Event::BaseUriUpdate { base_uri } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "cep47_base_uri_update".to_string());
+            param.insert("base_uri", base_uri.to_string());
+            events.push(param);
+        }
+        CEP47Event::TokenUriUpdate { token_id } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "cep47_token_uri_update".to_string());
+            param.insert("token_id", token_id.to_string());
+            events.push(param);
+        }
+        CEP47Event::ChecksumUpdate { token_id, checksum } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "cep47_checksum_update".to_string());
+            param.insert("token_id", token_id.to_string());
+            param.insert("algorithm", checksum.0.to_string());
+            param.insert("digest", checksum.1.to_string());
+            events.push(param);
+        }
+        CEP47Event::Reveal { base_uri, offset } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "cep47_reveal".to_string());
+            param.insert("base_uri", base_uri.to_string());
+            param.insert("offset", offset.to_string());
+            events.push(param);
+        }
+        CEP47Event::CollectionMetadataUpdate {
+            field,
+            changed_keys,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "cep47_collection_metadata_update".to_string());
+            param.insert("field", field.to_string());
+            param.insert("changed_keys", changed_keys.join(","));
+            events.push(param);
+        }
+        CEP47Event::PermanentAllMetadata => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "cep47_permanent_all_metadata".to_string());
             events.push(param);
         }
     };
@@ -1,32 +1,68 @@
-use alloc::vec::Vec;
-use casper_types::Key;
+use alloc::{string::String, vec::Vec};
+use casper_types::{Key, U256};
 
-use crate::TokenId;
+use crate::{Checksum, TokenId};
 
-pub enum CEP47Event {
+pub enum CEP47Event<Id = TokenId> {
     Mint {
         recipient: Key,
-        token_ids: Vec<TokenId>,
+        token_ids: Vec<Id>,
     },
     Burn {
         owner: Key,
-        token_ids: Vec<TokenId>,
+        token_ids: Vec<Id>,
     },
     Approve {
         owner: Key,
         spender: Key,
-        token_ids: Vec<TokenId>,
+        token_ids: Vec<Id>,
     },
     Revoke {
         owner: Key,
-        token_ids: Vec<TokenId>,
+        token_ids: Vec<Id>,
     },
     Transfer {
         sender: Key,
         recipient: Key,
-        token_ids: Vec<TokenId>,
+        token_ids: Vec<Id>,
+    },
+    Attach {
+        owner: Key,
+        parent_id: Id,
+        token_ids: Vec<Id>,
+    },
+    Detach {
+        owner: Key,
+        parent_id: Id,
+        token_ids: Vec<Id>,
     },
     MetadataUpdate {
-        token_id: TokenId,
+        token_id: Id,
+        changed_keys: Vec<String>,
+        version: u32,
+    },
+    PermanentMetadata {
+        token_ids: Vec<Id>,
+    },
+    PermanentAllMetadata,
+    BaseUriUpdate {
+        base_uri: String,
+    },
+    TokenUriUpdate {
+        token_id: Id,
+    },
+    ChecksumUpdate {
+        token_id: Id,
+        checksum: Checksum,
+    },
+    Reveal {
+        base_uri: String,
+        offset: U256,
+    },
+    /// `field` is `name`, `symbol` or `meta`; `changed_keys` lists the
+    /// collection meta keys that changed.
+    CollectionMetadataUpdate {
+        field: String,
+        changed_keys: Vec<String>,
     },
 }
@@ -3,13 +3,29 @@
 extern crate alloc;
 
 mod cep47;
+pub mod contract;
 pub mod data;
 pub mod event;
+mod phases;
+mod sale;
+mod schema;
+pub mod session;
+mod token_id;
 
-pub use cep47::{Error, CEP47};
+pub use cep47::{BurnPolicy, Error, RecipientPolicy, CEP47};
 pub use contract_utils;
+pub use phases::{MintPhase, Phases};
+pub use sale::Sale;
+pub use schema::MetaSchema;
+pub use token_id::TokenIdentifier;
 
 use alloc::{collections::BTreeMap, string::String};
 use casper_types::U256;
+/// The default, numeric token identifier.
 pub type TokenId = U256;
 pub type Meta = BTreeMap<String, String>;
+/// Digest algorithm tag and the hex encoded digest of a token's content.
+pub type Checksum = (String, String);
+/// Tier an admin listed a token id for sale in, `None` for the public mint
+/// price, and the metadata it is minted with.
+pub type SaleItem = (Option<String>, Meta);
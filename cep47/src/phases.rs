@@ -0,0 +1,176 @@
+use crate::{
+    data::{MintPhases, PhaseAllowlist, PhaseMints},
+    Error, Sale, TokenId, TokenIdentifier,
+};
+use alloc::{string::String, vec::Vec};
+use casper_contract::contract_api::runtime;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, Key, URef, U512,
+};
+use contract_utils::ContractStorage;
+
+/// A mint window restricted to allowlisted accounts. Membership is checked
+/// against `merkle_root` when set, otherwise against the phase allowlist.
+pub struct MintPhase {
+    pub start_time: u64,
+    pub end_time: u64,
+    /// Tokens a single account may mint in this phase, `0` for no limit.
+    pub wallet_limit: u32,
+    /// Price per token in motes, `0` for a free claim.
+    pub price: U512,
+    pub merkle_root: Option<[u8; 32]>,
+}
+
+impl ToBytes for MintPhase {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.start_time.to_bytes()?);
+        result.append(&mut self.end_time.to_bytes()?);
+        result.append(&mut self.wallet_limit.to_bytes()?);
+        result.append(&mut self.price.to_bytes()?);
+        result.append(&mut self.merkle_root.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.start_time.serialized_length()
+            + self.end_time.serialized_length()
+            + self.wallet_limit.serialized_length()
+            + self.price.serialized_length()
+            + self.merkle_root.serialized_length()
+    }
+}
+
+impl FromBytes for MintPhase {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (start_time, bytes) = u64::from_bytes(bytes)?;
+        let (end_time, bytes) = u64::from_bytes(bytes)?;
+        let (wallet_limit, bytes) = u32::from_bytes(bytes)?;
+        let (price, bytes) = U512::from_bytes(bytes)?;
+        let (merkle_root, bytes) = Option::<[u8; 32]>::from_bytes(bytes)?;
+        let phase = MintPhase {
+            start_time,
+            end_time,
+            wallet_limit,
+            price,
+            merkle_root,
+        };
+        Ok((phase, bytes))
+    }
+}
+
+impl CLTyped for MintPhase {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+/// Allowlisted mint phases layered over `CEP47::mint`. Configuring phases
+/// and allowlists is left to the implementing contract's access control.
+pub trait Phases<Storage: ContractStorage, Id: TokenIdentifier = TokenId>:
+    Sale<Storage, Id>
+{
+    fn mint_phase(&self, phase: String) -> Option<MintPhase> {
+        MintPhases::instance().get(&phase)
+    }
+
+    fn set_mint_phase(&mut self, phase: String, mint_phase: MintPhase) -> Result<(), Error> {
+        if mint_phase.start_time >= mint_phase.end_time {
+            return Err(Error::WrongArguments);
+        }
+        MintPhases::instance().set(&phase, mint_phase);
+        Ok(())
+    }
+
+    fn remove_mint_phase(&mut self, phase: String) {
+        MintPhases::instance().remove(&phase);
+    }
+
+    fn is_allowlisted(&self, phase: String, account: Key) -> bool {
+        PhaseAllowlist::instance().is_allowed(&phase, &account)
+    }
+
+    fn set_allowlist(&mut self, phase: String, accounts: Vec<Key>, allowed: bool) {
+        let allowlist_dict = PhaseAllowlist::instance();
+        for account in &accounts {
+            allowlist_dict.set(&phase, account, allowed);
+        }
+    }
+
+    fn phase_minted(&self, phase: String, account: Key) -> u32 {
+        PhaseMints::instance().get(&phase, &account)
+    }
+
+    /// Mints listed `token_ids` to the caller during an active `phase`, with
+    /// the metadata they were listed with. `purse` must hold the exact phase
+    /// price for paid phases, which applies whatever tier the ids were listed
+    /// in, and `proof` is only read when the phase uses a Merkle root.
+    fn phase_mint(
+        &mut self,
+        phase: String,
+        token_ids: Vec<Id>,
+        proof: Vec<[u8; 32]>,
+        purse: Option<URef>,
+    ) -> Result<Vec<Id>, Error> {
+        let mint_phase = self.mint_phase(phase.clone()).ok_or(Error::UnknownPhase)?;
+        let now: u64 = runtime::get_blocktime().into();
+        if now < mint_phase.start_time || now >= mint_phase.end_time {
+            return Err(Error::PhaseNotActive);
+        }
+
+        let caller = self.get_caller();
+        let allowed = match (mint_phase.merkle_root, caller.into_account()) {
+            (Some(root), Some(account)) => {
+                verify_merkle_proof(root, &proof, runtime::blake2b(account.as_bytes()))
+            }
+            (Some(_), None) => false,
+            (None, _) => self.is_allowlisted(phase.clone(), caller),
+        };
+        if !allowed {
+            return Err(Error::NotAllowlisted);
+        }
+
+        let phase_mints_dict = PhaseMints::instance();
+        let minted = phase_mints_dict
+            .get(&phase, &caller)
+            .checked_add(token_ids.len() as u32)
+            .ok_or(Error::WrongArguments)?;
+        if mint_phase.wallet_limit != 0 && minted > mint_phase.wallet_limit {
+            return Err(Error::PhaseLimitExceeded);
+        }
+
+        let price = mint_phase
+            .price
+            .checked_mul(U512::from(token_ids.len() as u64))
+            .ok_or(Error::WrongArguments)?;
+        if !price.is_zero() {
+            self.collect_payment(purse.ok_or(Error::WrongPaymentAmount)?, price)?;
+        }
+
+        let token_metas = self
+            .take_sale_items(&token_ids)?
+            .into_iter()
+            .map(|(_, token_meta)| token_meta)
+            .collect();
+        phase_mints_dict.set(&phase, &caller, minted);
+        self.mint(caller, token_ids, token_metas)
+    }
+}
+
+/// Checks `leaf` against `root` using sorted-pair blake2b hashing.
+fn verify_merkle_proof(root: [u8; 32], proof: &[[u8; 32]], leaf: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof {
+        let mut pair = Vec::with_capacity(64);
+        if computed <= *node {
+            pair.extend_from_slice(&computed);
+            pair.extend_from_slice(node);
+        } else {
+            pair.extend_from_slice(node);
+            pair.extend_from_slice(&computed);
+        }
+        computed = runtime::blake2b(pair);
+    }
+    computed == root
+}
@@ -0,0 +1,132 @@
+use crate::{
+    data::{self, SaleItems, TierPrices},
+    Error, Meta, SaleItem, TokenId, TokenIdentifier, CEP47,
+};
+use alloc::{string::String, vec::Vec};
+use casper_contract::{contract_api::system, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{account::AccountHash, Key, URef, U512};
+use contract_utils::ContractStorage;
+
+/// Paid minting into the contract purse. Access control for the setters and
+/// `withdraw` is left to the implementing contract.
+pub trait Sale<Storage: ContractStorage, Id: TokenIdentifier = TokenId>:
+    CEP47<Storage, Id>
+{
+    fn mint_price(&self) -> Option<U512> {
+        data::mint_price()
+    }
+
+    fn set_mint_price(&mut self, price: Option<U512>) {
+        data::set_mint_price(price);
+    }
+
+    fn tier_price(&self, tier: String) -> Option<U512> {
+        TierPrices::instance().get(&tier)
+    }
+
+    fn set_tier_price(&mut self, tier: String, price: Option<U512>) {
+        match price {
+            Some(price) => TierPrices::instance().set(&tier, price),
+            None => TierPrices::instance().remove(&tier),
+        }
+    }
+
+    fn treasury(&self) -> AccountHash {
+        data::treasury()
+    }
+
+    fn set_treasury(&mut self, treasury: AccountHash) {
+        data::set_treasury(treasury);
+    }
+
+    fn sale_item(&self, token_id: Id) -> Option<SaleItem> {
+        SaleItems::<Id>::instance().get(&token_id)
+    }
+
+    /// Lists `token_ids` for sale in `tier`, or at the public mint price when
+    /// `tier` is `None`, to be minted with `token_metas`.
+    fn list_sale_items(
+        &mut self,
+        token_ids: Vec<Id>,
+        token_metas: Vec<Meta>,
+        tier: Option<String>,
+    ) -> Result<(), Error> {
+        if token_ids.len() != token_metas.len() {
+            return Err(Error::WrongArguments);
+        }
+        if let Some(tier) = &tier {
+            TierPrices::instance().get(tier).ok_or(Error::UnknownTier)?;
+        }
+        let sale_items_dict = SaleItems::<Id>::instance();
+        for (token_id, token_meta) in token_ids.iter().zip(token_metas) {
+            if self.owner_of(token_id.clone()).is_some() {
+                return Err(Error::TokenIdAlreadyExists);
+            }
+            sale_items_dict.set(token_id, (tier.clone(), token_meta));
+        }
+        Ok(())
+    }
+
+    fn unlist_sale_items(&mut self, token_ids: Vec<Id>) {
+        let sale_items_dict = SaleItems::<Id>::instance();
+        for token_id in &token_ids {
+            sale_items_dict.remove(token_id);
+        }
+    }
+
+    /// Mints listed `token_ids` to `recipient` once `purse` holds exactly
+    /// their price. Each id costs the price of the tier it was listed in.
+    fn purchase_mint(
+        &mut self,
+        recipient: Key,
+        token_ids: Vec<Id>,
+        purse: URef,
+    ) -> Result<Vec<Id>, Error> {
+        let mut price = U512::zero();
+        let mut token_metas = Vec::with_capacity(token_ids.len());
+        for (tier, token_meta) in self.take_sale_items(&token_ids)? {
+            let unit_price = match tier {
+                Some(tier) => TierPrices::instance()
+                    .get(&tier)
+                    .ok_or(Error::UnknownTier)?,
+                None => data::mint_price().ok_or(Error::SaleNotActive)?,
+            };
+            price = price.checked_add(unit_price).ok_or(Error::WrongArguments)?;
+            token_metas.push(token_meta);
+        }
+        self.collect_payment(purse, price)?;
+
+        self.mint(recipient, token_ids, token_metas)
+    }
+
+    /// Removes the listings of `token_ids`, failing unless all of them are
+    /// listed.
+    fn take_sale_items(&mut self, token_ids: &[Id]) -> Result<Vec<SaleItem>, Error> {
+        let sale_items_dict = SaleItems::<Id>::instance();
+        let mut sale_items = Vec::with_capacity(token_ids.len());
+        for token_id in token_ids {
+            sale_items.push(
+                sale_items_dict
+                    .get(token_id)
+                    .ok_or(Error::TokenNotForSale)?,
+            );
+            sale_items_dict.remove(token_id);
+        }
+        Ok(sale_items)
+    }
+
+    /// Moves `price` from `purse` into the contract purse.
+    fn collect_payment(&mut self, purse: URef, price: U512) -> Result<(), Error> {
+        // Anything but the exact amount would be stranded in the buyer's purse.
+        if system::get_purse_balance(purse).unwrap_or_revert() != price {
+            return Err(Error::WrongPaymentAmount);
+        }
+        system::transfer_from_purse_to_purse(purse, data::purse(), price, None).unwrap_or_revert();
+        Ok(())
+    }
+
+    fn withdraw(&mut self, amount: U512) {
+        system::transfer_from_purse_to_account(data::purse(), data::treasury(), amount, None)
+            .unwrap_or_revert();
+    }
+}
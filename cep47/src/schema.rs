@@ -0,0 +1,95 @@
+use crate::{Error, Meta};
+use alloc::{string::String, vec::Vec};
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// Shape every token's metadata has to fit, fixed when the collection is
+/// installed. Lengths are counted in bytes.
+pub struct MetaSchema {
+    /// Keys every token has to carry.
+    pub required_keys: Vec<String>,
+    /// Keys a token may carry, `None` for any.
+    pub allowed_keys: Option<Vec<String>>,
+    pub max_key_length: Option<u32>,
+    pub max_value_length: Option<u32>,
+    /// Limit on the sum of all key and value lengths.
+    pub max_total_size: Option<u32>,
+}
+
+impl MetaSchema {
+    pub fn validate(&self, meta: &Meta) -> Result<(), Error> {
+        for key in &self.required_keys {
+            if !meta.contains_key(key) {
+                return Err(Error::MetadataKeyMissing);
+            }
+        }
+        let mut total_size: usize = 0;
+        for (key, value) in meta {
+            if let Some(allowed_keys) = &self.allowed_keys {
+                if !allowed_keys.contains(key) {
+                    return Err(Error::MetadataKeyNotAllowed);
+                }
+            }
+            if exceeds(key.len(), self.max_key_length)
+                || exceeds(value.len(), self.max_value_length)
+            {
+                return Err(Error::MetadataEntryTooLong);
+            }
+            total_size += key.len() + value.len();
+        }
+        if exceeds(total_size, self.max_total_size) {
+            return Err(Error::MetadataTooLarge);
+        }
+        Ok(())
+    }
+}
+
+fn exceeds(length: usize, limit: Option<u32>) -> bool {
+    matches!(limit, Some(limit) if length > limit as usize)
+}
+
+impl ToBytes for MetaSchema {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.required_keys.to_bytes()?);
+        result.append(&mut self.allowed_keys.to_bytes()?);
+        result.append(&mut self.max_key_length.to_bytes()?);
+        result.append(&mut self.max_value_length.to_bytes()?);
+        result.append(&mut self.max_total_size.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.required_keys.serialized_length()
+            + self.allowed_keys.serialized_length()
+            + self.max_key_length.serialized_length()
+            + self.max_value_length.serialized_length()
+            + self.max_total_size.serialized_length()
+    }
+}
+
+impl FromBytes for MetaSchema {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (required_keys, bytes) = Vec::<String>::from_bytes(bytes)?;
+        let (allowed_keys, bytes) = Option::<Vec<String>>::from_bytes(bytes)?;
+        let (max_key_length, bytes) = Option::<u32>::from_bytes(bytes)?;
+        let (max_value_length, bytes) = Option::<u32>::from_bytes(bytes)?;
+        let (max_total_size, bytes) = Option::<u32>::from_bytes(bytes)?;
+        let schema = MetaSchema {
+            required_keys,
+            allowed_keys,
+            max_key_length,
+            max_value_length,
+            max_total_size,
+        };
+        Ok((schema, bytes))
+    }
+}
+
+impl CLTyped for MetaSchema {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
@@ -0,0 +1,57 @@
+//! Session code paying for mints out of the caller's main purse, generic over
+//! the token identifier of the collection called.
+
+use alloc::{string::String, vec::Vec};
+use casper_contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractHash, Key, RuntimeArgs, URef, U512};
+
+use crate::TokenIdentifier;
+
+/// Moves `amount` out of the caller's main purse into a fresh purse and hands
+/// it to `purchase_mint`, since a contract can't debit the main purse itself.
+pub fn purchase_mint<Id: TokenIdentifier>() {
+    let nft_contract_hash: Key = runtime::get_named_arg("nft_contract_hash");
+    let amount: U512 = runtime::get_named_arg("amount");
+    let recipient: Key = runtime::get_named_arg("recipient");
+    let token_ids: Vec<Id> = runtime::get_named_arg("token_ids");
+
+    let _: () = runtime::call_contract(
+        ContractHash::new(nft_contract_hash.into_hash().unwrap_or_revert()),
+        "purchase_mint",
+        runtime_args! {
+            "recipient" => recipient,
+            "token_ids" => token_ids,
+            "purse" => funded_purse(amount)
+        },
+    );
+}
+
+/// Funds a fresh purse with `amount` and hands it to `phase_mint` for paid phases.
+pub fn phase_mint<Id: TokenIdentifier>() {
+    let nft_contract_hash: Key = runtime::get_named_arg("nft_contract_hash");
+    let amount: U512 = runtime::get_named_arg("amount");
+    let phase: String = runtime::get_named_arg("phase");
+    let token_ids: Vec<Id> = runtime::get_named_arg("token_ids");
+    let proof: Vec<[u8; 32]> = runtime::get_named_arg("proof");
+
+    let _: () = runtime::call_contract(
+        ContractHash::new(nft_contract_hash.into_hash().unwrap_or_revert()),
+        "phase_mint",
+        runtime_args! {
+            "phase" => phase,
+            "token_ids" => token_ids,
+            "proof" => proof,
+            "purse" => Some(funded_purse(amount))
+        },
+    );
+}
+
+fn funded_purse(amount: U512) -> URef {
+    let purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), purse, amount, None)
+        .unwrap_or_revert();
+    purse
+}
@@ -0,0 +1,54 @@
+use alloc::string::String;
+use casper_contract::contract_api::runtime;
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped, U256,
+};
+use core::fmt::Display;
+
+/// A type tokens can be identified by. Events carry the id's `Display` form.
+pub trait TokenIdentifier: Clone + PartialEq + Display + CLTyped + ToBytes + FromBytes {
+    /// Item key the token's entries are stored under, the same length for
+    /// every id of the type.
+    fn item_key(&self) -> String;
+
+    /// Position of the id in a sequentially numbered collection, `None` for
+    /// ids that aren't numbers.
+    fn as_index(&self) -> Option<U256>;
+
+    /// The id at position `index`, `None` for ids that aren't numbers.
+    fn from_index(index: U256) -> Option<Self>;
+}
+
+/// Numeric ids are keyed by their 32 big endian bytes in hex.
+impl TokenIdentifier for U256 {
+    fn item_key(&self) -> String {
+        let mut bytes = [0u8; 32];
+        self.to_big_endian(&mut bytes);
+        hex::encode(bytes)
+    }
+
+    fn as_index(&self) -> Option<U256> {
+        Some(*self)
+    }
+
+    fn from_index(index: U256) -> Option<Self> {
+        Some(index)
+    }
+}
+
+/// String ids, such as UUIDs or content hashes, are keyed by the blake2b
+/// digest of the string, so they may be of any length.
+impl TokenIdentifier for String {
+    fn item_key(&self) -> String {
+        hex::encode(runtime::blake2b(self.as_bytes()))
+    }
+
+    fn as_index(&self) -> Option<U256> {
+        None
+    }
+
+    fn from_index(_index: U256) -> Option<Self> {
+        None
+    }
+}
@@ -0,0 +1,187 @@
+#![no_main]
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, CLType, CLTyped, CLValue, ContractPackageHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U512,
+};
+use cep47::TokenId;
+use contract_utils::{ContractContext, OnChainContractStorage};
+use marketplace::Marketplace;
+
+#[derive(Default)]
+struct MarketplaceContract(OnChainContractStorage);
+
+impl ContractContext<OnChainContractStorage> for MarketplaceContract {
+    fn storage(&self) -> &OnChainContractStorage {
+        &self.0
+    }
+}
+
+impl Marketplace<OnChainContractStorage> for MarketplaceContract {}
+
+#[no_mangle]
+fn constructor() {
+    MarketplaceContract::default().init();
+}
+
+#[no_mangle]
+fn listing() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let ret = MarketplaceContract::default().listing(nft_contract, token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn list() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let price = runtime::get_named_arg::<U512>("price");
+    MarketplaceContract::default()
+        .list(nft_contract, token_id, price)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn cancel() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    MarketplaceContract::default()
+        .cancel(nft_contract, token_id)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn update_price() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let price = runtime::get_named_arg::<U512>("price");
+    MarketplaceContract::default()
+        .update_price(nft_contract, token_id, price)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn buy() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let purse = runtime::get_named_arg::<URef>("purse");
+    MarketplaceContract::default()
+        .buy(nft_contract, token_id, purse)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn call() {
+    let contract_name: String = runtime::get_named_arg("contract_name");
+
+    let (contract_hash, _) = storage::new_contract(
+        get_entry_points(),
+        None,
+        Some(format!("{}_contract_package_hash", contract_name)),
+        None,
+    );
+
+    let package_hash: ContractPackageHash = ContractPackageHash::new(
+        runtime::get_key(&format!("{}_contract_package_hash", contract_name))
+            .unwrap_or_revert()
+            .into_hash()
+            .unwrap_or_revert(),
+    );
+
+    let constructor_access: URef =
+        storage::create_contract_user_group(package_hash, "constructor", 1, Default::default())
+            .unwrap_or_revert()
+            .pop()
+            .unwrap_or_revert();
+
+    let _: () = runtime::call_contract(contract_hash, "constructor", runtime_args! {});
+
+    let mut urefs = BTreeSet::new();
+    urefs.insert(constructor_access);
+    storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
+        .unwrap_or_revert();
+
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash_wrapped", contract_name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "constructor",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("constructor")]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "listing",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+        ],
+        CLType::Option(Box::new(<(Key, U512)>::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "list",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+            Parameter::new("price", U512::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "update_price",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+            Parameter::new("price", U512::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "buy",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+            Parameter::new("purse", URef::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
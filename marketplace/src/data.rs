@@ -0,0 +1,129 @@
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime::get_call_stack, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{system::CallStackElement, ContractPackageHash, Key, URef, U512};
+use cep47::TokenId;
+use contract_utils::{key_and_value_to_str, Dict};
+
+use crate::event::MarketplaceEvent;
+
+const LISTINGS_DICT: &str = "listings";
+const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+
+/// Active listings as `(seller, price)`, keyed by NFT contract and token id.
+pub struct Listings {
+    dict: Dict,
+}
+
+impl Listings {
+    pub fn instance() -> Listings {
+        Listings {
+            dict: Dict::instance(LISTINGS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(LISTINGS_DICT)
+    }
+
+    pub fn get(&self, nft_contract: &Key, token_id: &TokenId) -> Option<(Key, U512)> {
+        self.dict.get(&key_and_value_to_str(nft_contract, token_id))
+    }
+
+    pub fn set(&self, nft_contract: &Key, token_id: &TokenId, value: (Key, U512)) {
+        self.dict
+            .set(&key_and_value_to_str(nft_contract, token_id), value);
+    }
+
+    pub fn remove(&self, nft_contract: &Key, token_id: &TokenId) {
+        self.dict
+            .remove::<(Key, U512)>(&key_and_value_to_str(nft_contract, token_id));
+    }
+}
+
+pub fn contract_package_hash() -> ContractPackageHash {
+    let call_stacks = get_call_stack();
+    let last_entry = call_stacks.last().unwrap_or_revert();
+    let package_hash: Option<ContractPackageHash> = match last_entry {
+        CallStackElement::StoredContract {
+            contract_package_hash,
+            contract_hash: _,
+        } => Some(*contract_package_hash),
+        _ => None,
+    };
+    package_hash.unwrap_or_revert()
+}
+
+pub fn emit(event: &MarketplaceEvent) {
+    let mut events = Vec::new();
+    let package = contract_package_hash();
+    match event {
+        MarketplaceEvent::List {
+            nft_contract,
+            token_id,
+            seller,
+            price,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "marketplace_list".to_string());
+            param.insert("nft_contract", nft_contract.to_string());
+            param.insert("token_id", token_id.to_string());
+            param.insert("seller", seller.to_string());
+            param.insert("price", price.to_string());
+            events.push(param);
+        }
+        MarketplaceEvent::Cancel {
+            nft_contract,
+            token_id,
+            seller,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "marketplace_cancel".to_string());
+            param.insert("nft_contract", nft_contract.to_string());
+            param.insert("token_id", token_id.to_string());
+            param.insert("seller", seller.to_string());
+            events.push(param);
+        }
+        MarketplaceEvent::PriceUpdate {
+            nft_contract,
+            token_id,
+            seller,
+            price,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "marketplace_price_update".to_string());
+            param.insert("nft_contract", nft_contract.to_string());
+            param.insert("token_id", token_id.to_string());
+            param.insert("seller", seller.to_string());
+            param.insert("price", price.to_string());
+            events.push(param);
+        }
+        MarketplaceEvent::Sale {
+            nft_contract,
+            token_id,
+            seller,
+            buyer,
+            price,
+            royalty,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "marketplace_sale".to_string());
+            param.insert("nft_contract", nft_contract.to_string());
+            param.insert("token_id", token_id.to_string());
+            param.insert("seller", seller.to_string());
+            param.insert("buyer", buyer.to_string());
+            param.insert("price", price.to_string());
+            param.insert("royalty", royalty.to_string());
+            events.push(param);
+        }
+    };
+    for param in events {
+        let _: URef = storage::new_uref(param);
+    }
+}
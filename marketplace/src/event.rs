@@ -0,0 +1,30 @@
+use casper_types::{Key, U512};
+use cep47::TokenId;
+
+pub enum MarketplaceEvent {
+    List {
+        nft_contract: Key,
+        token_id: TokenId,
+        seller: Key,
+        price: U512,
+    },
+    Cancel {
+        nft_contract: Key,
+        token_id: TokenId,
+        seller: Key,
+    },
+    PriceUpdate {
+        nft_contract: Key,
+        token_id: TokenId,
+        seller: Key,
+        price: U512,
+    },
+    Sale {
+        nft_contract: Key,
+        token_id: TokenId,
+        seller: Key,
+        buyer: Key,
+        price: U512,
+        royalty: U512,
+    },
+}
@@ -0,0 +1,14 @@
+#![no_std]
+extern crate alloc;
+
+pub mod data;
+pub mod event;
+mod marketplace;
+
+pub use contract_utils;
+pub use marketplace::{Error, Marketplace};
+
+/// Collection `meta` key holding the formatted account hash paid on resale.
+pub const ROYALTY_ACCOUNT: &str = "royalty_account";
+/// Collection `meta` key holding the royalty share in basis points.
+pub const ROYALTY_BPS: &str = "royalty_bps";
@@ -0,0 +1,201 @@
+use crate::{
+    data::{self, Listings},
+    event::MarketplaceEvent,
+    ROYALTY_ACCOUNT, ROYALTY_BPS,
+};
+use alloc::vec;
+use casper_contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::AccountHash, runtime_args, ApiError, ContractHash, Key, RuntimeArgs, URef, U512,
+};
+use cep47::{Meta, TokenId};
+use contract_utils::{ContractContext, ContractStorage};
+
+#[repr(u16)]
+pub enum Error {
+    PermissionDenied = 1,
+    WrongArguments = 2,
+    ListingNotFound = 3,
+    NotApproved = 4,
+    InvalidPrice = 5,
+    WrongPaymentAmount = 6,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+/// Fixed-price sales of CEP47 tokens. Sellers keep custody until a sale:
+/// listing requires the marketplace to be the approved spender, and `buy`
+/// moves the token with `transfer_from`. Token ids are `cep47::TokenId`, so
+/// only collections with numeric ids can be listed, not string id ones.
+pub trait Marketplace<Storage: ContractStorage>: ContractContext<Storage> {
+    fn init(&mut self) {
+        Listings::init();
+    }
+
+    fn listing(&self, nft_contract: Key, token_id: TokenId) -> Option<(Key, U512)> {
+        Listings::instance().get(&nft_contract, &token_id)
+    }
+
+    fn list(&mut self, nft_contract: Key, token_id: TokenId, price: U512) -> Result<(), Error> {
+        if price.is_zero() {
+            return Err(Error::InvalidPrice);
+        }
+        // Proceeds are paid out to the seller's main purse.
+        let seller = self.get_caller();
+        if seller.into_account().is_none() {
+            return Err(Error::PermissionDenied);
+        }
+
+        let nft_contract_hash = to_contract_hash(nft_contract)?;
+        let owner: Option<Key> = runtime::call_contract(
+            nft_contract_hash,
+            "owner_of",
+            runtime_args! { "token_id" => token_id },
+        );
+        if owner != Some(seller) {
+            return Err(Error::PermissionDenied);
+        }
+        let spender: Option<Key> = runtime::call_contract(
+            nft_contract_hash,
+            "get_approved",
+            runtime_args! { "owner" => seller, "token_id" => token_id },
+        );
+        if spender != Some(self.self_addr()) {
+            return Err(Error::NotApproved);
+        }
+
+        Listings::instance().set(&nft_contract, &token_id, (seller, price));
+        self.emit(MarketplaceEvent::List {
+            nft_contract,
+            token_id,
+            seller,
+            price,
+        });
+        Ok(())
+    }
+
+    fn cancel(&mut self, nft_contract: Key, token_id: TokenId) -> Result<(), Error> {
+        let seller = self.assert_seller(nft_contract, token_id)?;
+        Listings::instance().remove(&nft_contract, &token_id);
+        self.emit(MarketplaceEvent::Cancel {
+            nft_contract,
+            token_id,
+            seller,
+        });
+        Ok(())
+    }
+
+    fn update_price(
+        &mut self,
+        nft_contract: Key,
+        token_id: TokenId,
+        price: U512,
+    ) -> Result<(), Error> {
+        if price.is_zero() {
+            return Err(Error::InvalidPrice);
+        }
+        let seller = self.assert_seller(nft_contract, token_id)?;
+        Listings::instance().set(&nft_contract, &token_id, (seller, price));
+        self.emit(MarketplaceEvent::PriceUpdate {
+            nft_contract,
+            token_id,
+            seller,
+            price,
+        });
+        Ok(())
+    }
+
+    /// Pays the listing price out of `purse`, splitting off the collection
+    /// royalty when its `meta` declares one, and hands the token to the caller.
+    fn buy(&mut self, nft_contract: Key, token_id: TokenId, purse: URef) -> Result<(), Error> {
+        let listings_dict = Listings::instance();
+        let (seller, price) = listings_dict
+            .get(&nft_contract, &token_id)
+            .ok_or(Error::ListingNotFound)?;
+        let buyer = self.get_caller();
+        if buyer == seller {
+            return Err(Error::PermissionDenied);
+        }
+        if system::get_purse_balance(purse).unwrap_or_revert() != price {
+            return Err(Error::WrongPaymentAmount);
+        }
+        listings_dict.remove(&nft_contract, &token_id);
+
+        let nft_contract_hash = to_contract_hash(nft_contract)?;
+        let mut royalty = U512::zero();
+        if let Some((receiver, amount)) = royalty_of(nft_contract_hash, price) {
+            if !amount.is_zero() {
+                system::transfer_from_purse_to_account(purse, receiver, amount, None)
+                    .unwrap_or_revert();
+                royalty = amount;
+            }
+        }
+        system::transfer_from_purse_to_account(
+            purse,
+            seller.into_account().unwrap_or_revert(),
+            price - royalty,
+            None,
+        )
+        .unwrap_or_revert();
+
+        let _: () = runtime::call_contract(
+            nft_contract_hash,
+            "transfer_from",
+            runtime_args! {
+                "sender" => seller,
+                "recipient" => buyer,
+                "token_ids" => vec![token_id]
+            },
+        );
+
+        self.emit(MarketplaceEvent::Sale {
+            nft_contract,
+            token_id,
+            seller,
+            buyer,
+            price,
+            royalty,
+        });
+        Ok(())
+    }
+
+    fn assert_seller(&self, nft_contract: Key, token_id: TokenId) -> Result<Key, Error> {
+        let (seller, _) = self
+            .listing(nft_contract, token_id)
+            .ok_or(Error::ListingNotFound)?;
+        if seller != self.get_caller() {
+            return Err(Error::PermissionDenied);
+        }
+        Ok(seller)
+    }
+
+    fn emit(&mut self, event: MarketplaceEvent) {
+        data::emit(&event);
+    }
+}
+
+fn to_contract_hash(nft_contract: Key) -> Result<ContractHash, Error> {
+    nft_contract
+        .into_hash()
+        .map(ContractHash::new)
+        .ok_or(Error::WrongArguments)
+}
+
+/// Reads the royalty receiver and rate from the collection `meta`. Missing or
+/// malformed entries, or a rate above 100%, mean no royalty is paid.
+fn royalty_of(nft_contract_hash: ContractHash, price: U512) -> Option<(AccountHash, U512)> {
+    let meta: Meta = runtime::call_contract(nft_contract_hash, "meta", runtime_args! {});
+    let receiver = AccountHash::from_formatted_str(meta.get(ROYALTY_ACCOUNT)?).ok()?;
+    let bps: u64 = meta.get(ROYALTY_BPS)?.parse().ok()?;
+    if bps > 10_000 {
+        return None;
+    }
+    Some((receiver, price * U512::from(bps) / U512::from(10_000u64)))
+}
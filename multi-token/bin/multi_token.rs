@@ -0,0 +1,396 @@
+#![no_main]
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, CLType, CLTyped, CLValue, ContractPackageHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U256,
+};
+use contract_utils::{AdminControl, ContractContext, OnChainContractStorage};
+use multi_token::{Meta, MultiToken, TokenId};
+
+#[derive(Default)]
+struct MultiTokenContract(OnChainContractStorage);
+
+impl ContractContext<OnChainContractStorage> for MultiTokenContract {
+    fn storage(&self) -> &OnChainContractStorage {
+        &self.0
+    }
+}
+
+impl MultiToken<OnChainContractStorage> for MultiTokenContract {}
+impl AdminControl<OnChainContractStorage> for MultiTokenContract {}
+
+impl MultiTokenContract {
+    fn constructor(&mut self, name: String, symbol: String, meta: Meta) {
+        MultiToken::init(self, name, symbol, meta);
+        AdminControl::init(self);
+    }
+}
+
+#[no_mangle]
+fn constructor() {
+    let name = runtime::get_named_arg::<String>("name");
+    let symbol = runtime::get_named_arg::<String>("symbol");
+    let meta = runtime::get_named_arg::<Meta>("meta");
+    let admin = runtime::get_named_arg::<Key>("admin");
+    let mut contract = MultiTokenContract::default();
+    contract.constructor(name, symbol, meta);
+    contract.add_admin_without_checked(admin);
+}
+
+#[no_mangle]
+fn name() {
+    let ret = MultiTokenContract::default().name();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn symbol() {
+    let ret = MultiTokenContract::default().symbol();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn meta() {
+    let ret = MultiTokenContract::default().meta();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn total_supply() {
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let ret = MultiTokenContract::default().total_supply(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn balance_of() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let ret = MultiTokenContract::default().balance_of(owner, token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn balance_of_batch() {
+    let owners = runtime::get_named_arg::<Vec<Key>>("owners");
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let ret = MultiTokenContract::default()
+        .balance_of_batch(owners, token_ids)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn token_meta() {
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let ret = MultiTokenContract::default().token_meta(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn update_token_meta() {
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let token_meta = runtime::get_named_arg::<Meta>("token_meta");
+    let mut contract = MultiTokenContract::default();
+    contract.assert_caller_is_admin();
+    contract
+        .set_token_meta(token_id, token_meta)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn is_approved_for_all() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let operator = runtime::get_named_arg::<Key>("operator");
+    let ret = MultiTokenContract::default().is_approved_for_all(owner, operator);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn set_approval_for_all() {
+    let operator = runtime::get_named_arg::<Key>("operator");
+    let approved = runtime::get_named_arg::<bool>("approved");
+    MultiTokenContract::default()
+        .set_approval_for_all(operator, approved)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn mint() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let amounts = runtime::get_named_arg::<Vec<U256>>("amounts");
+    let token_metas = runtime::get_named_arg::<Vec<Meta>>("token_metas");
+    let mut contract = MultiTokenContract::default();
+    contract.assert_caller_is_admin();
+    contract
+        .mint(recipient, token_ids, amounts, token_metas)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn burn() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let amounts = runtime::get_named_arg::<Vec<U256>>("amounts");
+    MultiTokenContract::default()
+        .burn(owner, token_ids, amounts)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn transfer() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let amounts = runtime::get_named_arg::<Vec<U256>>("amounts");
+    MultiTokenContract::default()
+        .transfer(recipient, token_ids, amounts)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn transfer_from() {
+    let sender = runtime::get_named_arg::<Key>("sender");
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let amounts = runtime::get_named_arg::<Vec<U256>>("amounts");
+    MultiTokenContract::default()
+        .transfer_from(sender, recipient, token_ids, amounts)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn add_admin() {
+    let address = runtime::get_named_arg::<Key>("address");
+    MultiTokenContract::default().add_admin(address);
+}
+
+#[no_mangle]
+fn disable_admin() {
+    let address = runtime::get_named_arg::<Key>("address");
+    MultiTokenContract::default().disable_admin(address);
+}
+
+#[no_mangle]
+fn call() {
+    // Read arguments for the constructor call.
+    let name: String = runtime::get_named_arg("name");
+    let symbol: String = runtime::get_named_arg("symbol");
+    let meta: Meta = runtime::get_named_arg("meta");
+    let contract_name: String = runtime::get_named_arg("contract_name");
+
+    // The installing account becomes the first admin.
+    let constructor_args = runtime_args! {
+        "name" => name,
+        "symbol" => symbol,
+        "meta" => meta,
+        "admin" => Key::from(runtime::get_caller())
+    };
+
+    let (contract_hash, _) = storage::new_contract(
+        get_entry_points(),
+        None,
+        Some(format!("{}_contract_package_hash", contract_name)),
+        None,
+    );
+
+    let package_hash: ContractPackageHash = ContractPackageHash::new(
+        runtime::get_key(&format!("{}_contract_package_hash", contract_name))
+            .unwrap_or_revert()
+            .into_hash()
+            .unwrap_or_revert(),
+    );
+
+    let constructor_access: URef =
+        storage::create_contract_user_group(package_hash, "constructor", 1, Default::default())
+            .unwrap_or_revert()
+            .pop()
+            .unwrap_or_revert();
+
+    let _: () = runtime::call_contract(contract_hash, "constructor", constructor_args);
+
+    let mut urefs = BTreeSet::new();
+    urefs.insert(constructor_access);
+    storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
+        .unwrap_or_revert();
+
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash_wrapped", contract_name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "constructor",
+        vec![
+            Parameter::new("name", String::cl_type()),
+            Parameter::new("symbol", String::cl_type()),
+            Parameter::new("meta", Meta::cl_type()),
+            Parameter::new("admin", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("constructor")]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "name",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "symbol",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "meta",
+        vec![],
+        Meta::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_supply",
+        vec![Parameter::new("token_id", TokenId::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of_batch",
+        vec![
+            Parameter::new("owners", CLType::List(Box::new(Key::cl_type()))),
+            Parameter::new("token_ids", CLType::List(Box::new(TokenId::cl_type()))),
+        ],
+        CLType::List(Box::new(U256::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "token_meta",
+        vec![Parameter::new("token_id", TokenId::cl_type())],
+        CLType::Option(Box::new(Meta::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "update_token_meta",
+        vec![
+            Parameter::new("token_id", TokenId::cl_type()),
+            Parameter::new("token_meta", Meta::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_approved_for_all",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("operator", Key::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_approval_for_all",
+        vec![
+            Parameter::new("operator", Key::cl_type()),
+            Parameter::new("approved", bool::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(TokenId::cl_type()))),
+            Parameter::new("amounts", CLType::List(Box::new(U256::cl_type()))),
+            Parameter::new("token_metas", CLType::List(Box::new(Meta::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "burn",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(TokenId::cl_type()))),
+            Parameter::new("amounts", CLType::List(Box::new(U256::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(TokenId::cl_type()))),
+            Parameter::new("amounts", CLType::List(Box::new(U256::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_from",
+        vec![
+            Parameter::new("sender", Key::cl_type()),
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(TokenId::cl_type()))),
+            Parameter::new("amounts", CLType::List(Box::new(U256::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "add_admin",
+        vec![Parameter::new("address", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "disable_admin",
+        vec![Parameter::new("address", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
@@ -0,0 +1,244 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_contract::{
+    contract_api::{runtime::get_call_stack, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{system::CallStackElement, ContractPackageHash, Key, URef, U256};
+use cep47::TokenIdentifier;
+use contract_utils::{get_key, key_and_value_to_str, set_key, Dict};
+
+use crate::{event::MultiTokenEvent, Meta, TokenId};
+
+const BALANCES_DICT: &str = "balances";
+const OPERATORS_DICT: &str = "operators";
+const METADATA_DICT: &str = "metadata";
+const SUPPLIES_DICT: &str = "supplies";
+const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+
+pub const NAME: &str = "name";
+pub const META: &str = "meta";
+pub const SYMBOL: &str = "symbol";
+
+pub struct Balances {
+    dict: Dict,
+}
+
+impl Balances {
+    pub fn instance() -> Balances {
+        Balances {
+            dict: Dict::instance(BALANCES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(BALANCES_DICT)
+    }
+
+    pub fn get(&self, owner: &Key, token_id: &TokenId) -> U256 {
+        self.dict
+            .get(&key_and_value_to_str(owner, token_id))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, token_id: &TokenId, value: U256) {
+        self.dict.set(&key_and_value_to_str(owner, token_id), value);
+    }
+}
+
+pub struct Operators {
+    dict: Dict,
+}
+
+impl Operators {
+    pub fn instance() -> Operators {
+        Operators {
+            dict: Dict::instance(OPERATORS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(OPERATORS_DICT)
+    }
+
+    pub fn is_operator(&self, owner: &Key, operator: &Key) -> bool {
+        self.dict
+            .get_by_keys::<bool>((owner, operator))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, operator: &Key, approved: bool) {
+        if approved {
+            self.dict.set_by_keys((owner, operator), true);
+        } else {
+            self.dict.remove_by_vec_of_keys::<bool>((owner, operator));
+        }
+    }
+}
+
+pub struct Metadata {
+    dict: Dict,
+}
+
+impl Metadata {
+    pub fn instance() -> Metadata {
+        Metadata {
+            dict: Dict::instance(METADATA_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(METADATA_DICT)
+    }
+
+    pub fn get(&self, key: &TokenId) -> Option<Meta> {
+        self.dict.get(&key.item_key())
+    }
+
+    pub fn set(&self, key: &TokenId, value: Meta) {
+        self.dict.set(&key.item_key(), value);
+    }
+}
+
+pub struct Supplies {
+    dict: Dict,
+}
+
+impl Supplies {
+    pub fn instance() -> Supplies {
+        Supplies {
+            dict: Dict::instance(SUPPLIES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(SUPPLIES_DICT)
+    }
+
+    pub fn get(&self, key: &TokenId) -> U256 {
+        self.dict.get(&key.item_key()).unwrap_or_default()
+    }
+
+    pub fn set(&self, key: &TokenId, value: U256) {
+        self.dict.set(&key.item_key(), value);
+    }
+}
+
+pub fn name() -> String {
+    get_key(NAME).unwrap_or_revert()
+}
+
+pub fn set_name(name: String) {
+    set_key(NAME, name);
+}
+
+pub fn symbol() -> String {
+    get_key(SYMBOL).unwrap_or_revert()
+}
+
+pub fn set_symbol(symbol: String) {
+    set_key(SYMBOL, symbol);
+}
+
+pub fn meta() -> Meta {
+    get_key(META).unwrap_or_revert()
+}
+
+pub fn set_meta(meta: Meta) {
+    set_key(META, meta);
+}
+
+pub fn contract_package_hash() -> ContractPackageHash {
+    let call_stacks = get_call_stack();
+    let last_entry = call_stacks.last().unwrap_or_revert();
+    let package_hash: Option<ContractPackageHash> = match last_entry {
+        CallStackElement::StoredContract {
+            contract_package_hash,
+            contract_hash: _,
+        } => Some(*contract_package_hash),
+        _ => None,
+    };
+    package_hash.unwrap_or_revert()
+}
+
+pub fn emit(event: &MultiTokenEvent) {
+    let mut events = Vec::new();
+    let package = contract_package_hash();
+    match event {
+        MultiTokenEvent::Mint {
+            recipient,
+            token_ids,
+            amounts,
+        } => {
+            for (token_id, amount) in token_ids.iter().zip(amounts) {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "multi_token_mint".to_string());
+                param.insert("recipient", recipient.to_string());
+                param.insert("token_id", token_id.to_string());
+                param.insert("amount", amount.to_string());
+                events.push(param);
+            }
+        }
+        MultiTokenEvent::Burn {
+            owner,
+            token_ids,
+            amounts,
+        } => {
+            for (token_id, amount) in token_ids.iter().zip(amounts) {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "multi_token_burn".to_string());
+                param.insert("owner", owner.to_string());
+                param.insert("token_id", token_id.to_string());
+                param.insert("amount", amount.to_string());
+                events.push(param);
+            }
+        }
+        MultiTokenEvent::Transfer {
+            operator,
+            sender,
+            recipient,
+            token_ids,
+            amounts,
+        } => {
+            for (token_id, amount) in token_ids.iter().zip(amounts) {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "multi_token_transfer".to_string());
+                param.insert("operator", operator.to_string());
+                param.insert("sender", sender.to_string());
+                param.insert("recipient", recipient.to_string());
+                param.insert("token_id", token_id.to_string());
+                param.insert("amount", amount.to_string());
+                events.push(param);
+            }
+        }
+        MultiTokenEvent::ApprovalForAll {
+            owner,
+            operator,
+            approved,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "multi_token_approval_for_all".to_string());
+            param.insert("owner", owner.to_string());
+            param.insert("operator", operator.to_string());
+            param.insert("approved", approved.to_string());
+            events.push(param);
+        }
+        MultiTokenEvent::MetadataUpdate { token_id } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "multi_token_metadata_update".to_string());
+            param.insert("token_id", token_id.to_string());
+            events.push(param);
+        }
+    };
+    for param in events {
+        let _: URef = storage::new_uref(param);
+    }
+}
@@ -0,0 +1,32 @@
+use alloc::vec::Vec;
+use casper_types::{Key, U256};
+
+use crate::TokenId;
+
+pub enum MultiTokenEvent {
+    Mint {
+        recipient: Key,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+    },
+    Burn {
+        owner: Key,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+    },
+    Transfer {
+        operator: Key,
+        sender: Key,
+        recipient: Key,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+    },
+    ApprovalForAll {
+        owner: Key,
+        operator: Key,
+        approved: bool,
+    },
+    MetadataUpdate {
+        token_id: TokenId,
+    },
+}
@@ -0,0 +1,14 @@
+#![no_std]
+extern crate alloc;
+
+pub mod data;
+pub mod event;
+mod multi_token;
+
+pub use contract_utils;
+pub use multi_token::{Error, MultiToken};
+
+use alloc::{collections::BTreeMap, string::String};
+use casper_types::U256;
+pub type TokenId = U256;
+pub type Meta = BTreeMap<String, String>;
@@ -0,0 +1,257 @@
+use crate::{
+    data::{self, Balances, Metadata, Operators, Supplies},
+    event::MultiTokenEvent,
+    Meta, TokenId,
+};
+use alloc::{string::String, vec::Vec};
+use casper_types::{ApiError, Key, U256};
+use contract_utils::{ContractContext, ContractStorage};
+
+#[repr(u16)]
+pub enum Error {
+    PermissionDenied = 1,
+    WrongArguments = 2,
+    InsufficientBalance = 3,
+    TokenIdDoesntExist = 4,
+    SelfApproval = 5,
+    SupplyOverflow = 6,
+    ZeroAmount = 7,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+pub trait MultiToken<Storage: ContractStorage>: ContractContext<Storage> {
+    fn init(&mut self, name: String, symbol: String, meta: Meta) {
+        data::set_name(name);
+        data::set_symbol(symbol);
+        data::set_meta(meta);
+        Balances::init();
+        Operators::init();
+        Metadata::init();
+        Supplies::init();
+    }
+
+    fn name(&self) -> String {
+        data::name()
+    }
+
+    fn symbol(&self) -> String {
+        data::symbol()
+    }
+
+    fn meta(&self) -> Meta {
+        data::meta()
+    }
+
+    fn total_supply(&self, token_id: TokenId) -> U256 {
+        Supplies::instance().get(&token_id)
+    }
+
+    fn balance_of(&self, owner: Key, token_id: TokenId) -> U256 {
+        Balances::instance().get(&owner, &token_id)
+    }
+
+    fn balance_of_batch(
+        &self,
+        owners: Vec<Key>,
+        token_ids: Vec<TokenId>,
+    ) -> Result<Vec<U256>, Error> {
+        if owners.len() != token_ids.len() {
+            return Err(Error::WrongArguments);
+        }
+        let balances_dict = Balances::instance();
+        Ok(owners
+            .iter()
+            .zip(&token_ids)
+            .map(|(owner, token_id)| balances_dict.get(owner, token_id))
+            .collect())
+    }
+
+    fn token_meta(&self, token_id: TokenId) -> Option<Meta> {
+        Metadata::instance().get(&token_id)
+    }
+
+    fn set_token_meta(&mut self, token_id: TokenId, meta: Meta) -> Result<(), Error> {
+        if self.total_supply(token_id).is_zero() {
+            return Err(Error::TokenIdDoesntExist);
+        }
+        Metadata::instance().set(&token_id, meta);
+        self.emit(MultiTokenEvent::MetadataUpdate { token_id });
+        Ok(())
+    }
+
+    fn is_approved_for_all(&self, owner: Key, operator: Key) -> bool {
+        Operators::instance().is_operator(&owner, &operator)
+    }
+
+    fn set_approval_for_all(&mut self, operator: Key, approved: bool) -> Result<(), Error> {
+        let owner = self.get_caller();
+        if owner == operator {
+            return Err(Error::SelfApproval);
+        }
+        Operators::instance().set(&owner, &operator, approved);
+        self.emit(MultiTokenEvent::ApprovalForAll {
+            owner,
+            operator,
+            approved,
+        });
+        Ok(())
+    }
+
+    /// Mints `amounts` of each id; `token_metas` is applied only to ids that
+    /// have no supply yet, so minting more of an existing id keeps its metadata.
+    /// Zero amounts are rejected, as they would store metadata for an id that
+    /// still has no supply.
+    fn mint(
+        &mut self,
+        recipient: Key,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+        token_metas: Vec<Meta>,
+    ) -> Result<(), Error> {
+        if token_ids.len() != amounts.len() || token_ids.len() != token_metas.len() {
+            return Err(Error::WrongArguments);
+        }
+
+        let balances_dict = Balances::instance();
+        let supplies_dict = Supplies::instance();
+        let metadata_dict = Metadata::instance();
+
+        for ((token_id, amount), token_meta) in token_ids.iter().zip(&amounts).zip(token_metas) {
+            if amount.is_zero() {
+                return Err(Error::ZeroAmount);
+            }
+            let supply = supplies_dict.get(token_id);
+            if supply.is_zero() {
+                metadata_dict.set(token_id, token_meta);
+            }
+            let new_supply = supply.checked_add(*amount).ok_or(Error::SupplyOverflow)?;
+            supplies_dict.set(token_id, new_supply);
+            let balance = balances_dict
+                .get(&recipient, token_id)
+                .checked_add(*amount)
+                .ok_or(Error::SupplyOverflow)?;
+            balances_dict.set(&recipient, token_id, balance);
+        }
+
+        self.emit(MultiTokenEvent::Mint {
+            recipient,
+            token_ids,
+            amounts,
+        });
+        Ok(())
+    }
+
+    fn burn(
+        &mut self,
+        owner: Key,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+    ) -> Result<(), Error> {
+        let spender = self.get_caller();
+        if spender != owner && !self.is_approved_for_all(owner, spender) {
+            return Err(Error::PermissionDenied);
+        }
+        self.burn_internal(owner, token_ids, amounts)
+    }
+
+    fn burn_internal(
+        &mut self,
+        owner: Key,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+    ) -> Result<(), Error> {
+        if token_ids.len() != amounts.len() {
+            return Err(Error::WrongArguments);
+        }
+
+        let balances_dict = Balances::instance();
+        let supplies_dict = Supplies::instance();
+
+        for (token_id, amount) in token_ids.iter().zip(&amounts) {
+            let balance = balances_dict
+                .get(&owner, token_id)
+                .checked_sub(*amount)
+                .ok_or(Error::InsufficientBalance)?;
+            balances_dict.set(&owner, token_id, balance);
+            let supply = supplies_dict.get(token_id).checked_sub(*amount).unwrap();
+            supplies_dict.set(token_id, supply);
+        }
+
+        self.emit(MultiTokenEvent::Burn {
+            owner,
+            token_ids,
+            amounts,
+        });
+        Ok(())
+    }
+
+    fn transfer(
+        &mut self,
+        recipient: Key,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+    ) -> Result<(), Error> {
+        self.transfer_from(self.get_caller(), recipient, token_ids, amounts)
+    }
+
+    fn transfer_from(
+        &mut self,
+        owner: Key,
+        recipient: Key,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+    ) -> Result<(), Error> {
+        let operator = self.get_caller();
+        if operator != owner && !self.is_approved_for_all(owner, operator) {
+            return Err(Error::PermissionDenied);
+        }
+        self.transfer_from_internal(operator, owner, recipient, token_ids, amounts)
+    }
+
+    fn transfer_from_internal(
+        &mut self,
+        operator: Key,
+        owner: Key,
+        recipient: Key,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U256>,
+    ) -> Result<(), Error> {
+        if token_ids.len() != amounts.len() {
+            return Err(Error::WrongArguments);
+        }
+
+        let balances_dict = Balances::instance();
+
+        // Balances are re-read per entry so repeated ids in one batch add up.
+        for (token_id, amount) in token_ids.iter().zip(&amounts) {
+            let owner_balance = balances_dict
+                .get(&owner, token_id)
+                .checked_sub(*amount)
+                .ok_or(Error::InsufficientBalance)?;
+            balances_dict.set(&owner, token_id, owner_balance);
+            let recipient_balance = balances_dict
+                .get(&recipient, token_id)
+                .checked_add(*amount)
+                .unwrap();
+            balances_dict.set(&recipient, token_id, recipient_balance);
+        }
+
+        self.emit(MultiTokenEvent::Transfer {
+            operator,
+            sender: owner,
+            recipient,
+            token_ids,
+            amounts,
+        });
+        Ok(())
+    }
+
+    fn emit(&mut self, event: MultiTokenEvent) {
+        data::emit(&event);
+    }
+}
@@ -0,0 +1,381 @@
+#![no_main]
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, CLType, CLTyped, CLValue, ContractPackageHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U256,
+};
+use cep47::TokenId;
+use contract_utils::{ContractContext, OnChainContractStorage};
+use staking::Staking;
+
+#[derive(Default)]
+struct StakingContract(OnChainContractStorage);
+
+impl ContractContext<OnChainContractStorage> for StakingContract {
+    fn storage(&self) -> &OnChainContractStorage {
+        &self.0
+    }
+}
+
+impl Staking<OnChainContractStorage> for StakingContract {}
+
+#[no_mangle]
+fn constructor() {
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let reward_rate = runtime::get_named_arg::<U256>("reward_rate");
+    let name = runtime::get_named_arg::<String>("name");
+    let symbol = runtime::get_named_arg::<String>("symbol");
+    let decimals = runtime::get_named_arg::<u8>("decimals");
+    StakingContract::default().init(nft_contract, reward_rate, name, symbol, decimals);
+}
+
+#[no_mangle]
+fn nft_contract() {
+    let ret = StakingContract::default().nft_contract();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn reward_rate() {
+    let ret = StakingContract::default().reward_rate();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn name() {
+    let ret = StakingContract::default().name();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn symbol() {
+    let ret = StakingContract::default().symbol();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn decimals() {
+    let ret = StakingContract::default().decimals();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn total_supply() {
+    let ret = StakingContract::default().total_supply();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn balance_of() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let ret = StakingContract::default().balance_of(owner);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn transfer() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let amount = runtime::get_named_arg::<U256>("amount");
+    StakingContract::default()
+        .transfer(recipient, amount)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn approve() {
+    let spender = runtime::get_named_arg::<Key>("spender");
+    let amount = runtime::get_named_arg::<U256>("amount");
+    StakingContract::default().approve(spender, amount);
+}
+
+#[no_mangle]
+fn allowance() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let spender = runtime::get_named_arg::<Key>("spender");
+    let ret = StakingContract::default().allowance(owner, spender);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn transfer_from() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let amount = runtime::get_named_arg::<U256>("amount");
+    StakingContract::default()
+        .transfer_from(owner, recipient, amount)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn staker_of() {
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let ret = StakingContract::default().staker_of(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn staked_tokens() {
+    let staker = runtime::get_named_arg::<Key>("staker");
+    let ret = StakingContract::default().staked_tokens(staker);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn pending_rewards() {
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let ret = StakingContract::default()
+        .pending_rewards(token_ids)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn stake() {
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    StakingContract::default()
+        .stake(token_ids)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn claim() {
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let ret = StakingContract::default()
+        .claim(token_ids)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn unstake() {
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    StakingContract::default()
+        .unstake(token_ids)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn call() {
+    // Read arguments for the constructor call.
+    let nft_contract: Key = runtime::get_named_arg("nft_contract");
+    let reward_rate: U256 = runtime::get_named_arg("reward_rate");
+    let name: String = runtime::get_named_arg("name");
+    let symbol: String = runtime::get_named_arg("symbol");
+    let decimals: u8 = runtime::get_named_arg("decimals");
+    let contract_name: String = runtime::get_named_arg("contract_name");
+
+    let constructor_args = runtime_args! {
+        "nft_contract" => nft_contract,
+        "reward_rate" => reward_rate,
+        "name" => name,
+        "symbol" => symbol,
+        "decimals" => decimals
+    };
+
+    let (contract_hash, _) = storage::new_contract(
+        get_entry_points(),
+        None,
+        Some(format!("{}_contract_package_hash", contract_name)),
+        None,
+    );
+
+    let package_hash: ContractPackageHash = ContractPackageHash::new(
+        runtime::get_key(&format!("{}_contract_package_hash", contract_name))
+            .unwrap_or_revert()
+            .into_hash()
+            .unwrap_or_revert(),
+    );
+
+    let constructor_access: URef =
+        storage::create_contract_user_group(package_hash, "constructor", 1, Default::default())
+            .unwrap_or_revert()
+            .pop()
+            .unwrap_or_revert();
+
+    let _: () = runtime::call_contract(contract_hash, "constructor", constructor_args);
+
+    let mut urefs = BTreeSet::new();
+    urefs.insert(constructor_access);
+    storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
+        .unwrap_or_revert();
+
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash_wrapped", contract_name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "constructor",
+        vec![
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("reward_rate", U256::cl_type()),
+            Parameter::new("name", String::cl_type()),
+            Parameter::new("symbol", String::cl_type()),
+            Parameter::new("decimals", u8::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("constructor")]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "nft_contract",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "reward_rate",
+        vec![],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "name",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "symbol",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "decimals",
+        vec![],
+        u8::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_supply",
+        vec![],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of",
+        vec![Parameter::new("owner", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "approve",
+        vec![
+            Parameter::new("spender", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "allowance",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("spender", Key::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_from",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "staker_of",
+        vec![Parameter::new("token_id", TokenId::cl_type())],
+        CLType::Option(Box::new(Key::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "staked_tokens",
+        vec![Parameter::new("staker", Key::cl_type())],
+        CLType::List(Box::new(TokenId::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "pending_rewards",
+        vec![Parameter::new(
+            "token_ids",
+            CLType::List(Box::new(TokenId::cl_type())),
+        )],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "stake",
+        vec![Parameter::new(
+            "token_ids",
+            CLType::List(Box::new(TokenId::cl_type())),
+        )],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "claim",
+        vec![Parameter::new(
+            "token_ids",
+            CLType::List(Box::new(TokenId::cl_type())),
+        )],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "unstake",
+        vec![Parameter::new(
+            "token_ids",
+            CLType::List(Box::new(TokenId::cl_type())),
+        )],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
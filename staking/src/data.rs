@@ -0,0 +1,261 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_contract::{
+    contract_api::{runtime::get_call_stack, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{system::CallStackElement, ContractPackageHash, Key, URef, U256};
+use cep47::{TokenId, TokenIdentifier};
+use contract_utils::{get_key, set_key, Dict};
+
+use crate::event::StakingEvent;
+
+const STAKES_DICT: &str = "stakes";
+const STAKED_TOKENS_DICT: &str = "staked_tokens";
+const BALANCES_DICT: &str = "balances";
+const ALLOWANCES_DICT: &str = "allowances";
+const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+
+pub const NFT_CONTRACT: &str = "nft_contract";
+pub const REWARD_RATE: &str = "reward_rate";
+pub const NAME: &str = "name";
+pub const SYMBOL: &str = "symbol";
+pub const DECIMALS: &str = "decimals";
+pub const TOTAL_SUPPLY: &str = "total_supply";
+
+/// Staker, the block time (ms) rewards were last settled and the unpaid
+/// thousandths of a reward unit carried over from that settlement, for
+/// each staked token.
+pub struct Stakes {
+    dict: Dict,
+}
+
+impl Stakes {
+    pub fn instance() -> Stakes {
+        Stakes {
+            dict: Dict::instance(STAKES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(STAKES_DICT)
+    }
+
+    pub fn get(&self, token_id: &TokenId) -> Option<(Key, u64, u64)> {
+        self.dict.get(&token_id.item_key())
+    }
+
+    pub fn set(&self, token_id: &TokenId, value: (Key, u64, u64)) {
+        self.dict.set(&token_id.item_key(), value);
+    }
+
+    pub fn remove(&self, token_id: &TokenId) {
+        self.dict.remove::<(Key, u64, u64)>(&token_id.item_key());
+    }
+}
+
+pub struct StakedTokens {
+    dict: Dict,
+}
+
+impl StakedTokens {
+    pub fn instance() -> StakedTokens {
+        StakedTokens {
+            dict: Dict::instance(STAKED_TOKENS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(STAKED_TOKENS_DICT)
+    }
+
+    pub fn get(&self, staker: &Key) -> Vec<TokenId> {
+        self.dict.get_by_key(staker).unwrap_or_default()
+    }
+
+    pub fn set(&self, staker: &Key, value: Vec<TokenId>) {
+        if value.is_empty() {
+            self.dict.remove_by_key::<Vec<TokenId>>(staker);
+        } else {
+            self.dict.set_by_key(staker, value);
+        }
+    }
+}
+
+/// Reward token balances.
+pub struct Balances {
+    dict: Dict,
+}
+
+impl Balances {
+    pub fn instance() -> Balances {
+        Balances {
+            dict: Dict::instance(BALANCES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(BALANCES_DICT)
+    }
+
+    pub fn get(&self, owner: &Key) -> U256 {
+        self.dict.get_by_key(owner).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, value: U256) {
+        self.dict.set_by_key(owner, value);
+    }
+}
+
+/// Reward token amounts owners let spenders move with `transfer_from`.
+pub struct Allowances {
+    dict: Dict,
+}
+
+impl Allowances {
+    pub fn instance() -> Allowances {
+        Allowances {
+            dict: Dict::instance(ALLOWANCES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(ALLOWANCES_DICT)
+    }
+
+    pub fn get(&self, owner: &Key, spender: &Key) -> U256 {
+        self.dict.get_by_keys((owner, spender)).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, spender: &Key, value: U256) {
+        self.dict.set_by_keys((owner, spender), value);
+    }
+}
+
+pub fn nft_contract() -> Key {
+    get_key(NFT_CONTRACT).unwrap_or_revert()
+}
+
+pub fn set_nft_contract(nft_contract: Key) {
+    set_key(NFT_CONTRACT, nft_contract);
+}
+
+pub fn reward_rate() -> U256 {
+    get_key(REWARD_RATE).unwrap_or_revert()
+}
+
+pub fn set_reward_rate(reward_rate: U256) {
+    set_key(REWARD_RATE, reward_rate);
+}
+
+pub fn name() -> String {
+    get_key(NAME).unwrap_or_revert()
+}
+
+pub fn set_name(name: String) {
+    set_key(NAME, name);
+}
+
+pub fn symbol() -> String {
+    get_key(SYMBOL).unwrap_or_revert()
+}
+
+pub fn set_symbol(symbol: String) {
+    set_key(SYMBOL, symbol);
+}
+
+pub fn decimals() -> u8 {
+    get_key(DECIMALS).unwrap_or_revert()
+}
+
+pub fn set_decimals(decimals: u8) {
+    set_key(DECIMALS, decimals);
+}
+
+pub fn total_supply() -> U256 {
+    get_key(TOTAL_SUPPLY).unwrap_or_default()
+}
+
+pub fn set_total_supply(total_supply: U256) {
+    set_key(TOTAL_SUPPLY, total_supply);
+}
+
+pub fn contract_package_hash() -> ContractPackageHash {
+    let call_stacks = get_call_stack();
+    let last_entry = call_stacks.last().unwrap_or_revert();
+    let package_hash: Option<ContractPackageHash> = match last_entry {
+        CallStackElement::StoredContract {
+            contract_package_hash,
+            contract_hash: _,
+        } => Some(*contract_package_hash),
+        _ => None,
+    };
+    package_hash.unwrap_or_revert()
+}
+
+pub fn emit(event: &StakingEvent) {
+    let mut events = Vec::new();
+    let package = contract_package_hash();
+    match event {
+        StakingEvent::Stake { staker, token_ids } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "staking_stake".to_string());
+                param.insert("staker", staker.to_string());
+                param.insert("token_id", token_id.to_string());
+                events.push(param);
+            }
+        }
+        StakingEvent::Unstake { staker, token_ids } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "staking_unstake".to_string());
+                param.insert("staker", staker.to_string());
+                param.insert("token_id", token_id.to_string());
+                events.push(param);
+            }
+        }
+        StakingEvent::Claim { staker, amount } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "staking_claim".to_string());
+            param.insert("staker", staker.to_string());
+            param.insert("amount", amount.to_string());
+            events.push(param);
+        }
+        StakingEvent::Approve {
+            owner,
+            spender,
+            amount,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "staking_approve".to_string());
+            param.insert("owner", owner.to_string());
+            param.insert("spender", spender.to_string());
+            param.insert("amount", amount.to_string());
+            events.push(param);
+        }
+        StakingEvent::Transfer {
+            sender,
+            recipient,
+            amount,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "staking_transfer".to_string());
+            param.insert("sender", sender.to_string());
+            param.insert("recipient", recipient.to_string());
+            param.insert("amount", amount.to_string());
+            events.push(param);
+        }
+    };
+    for param in events {
+        let _: URef = storage::new_uref(param);
+    }
+}
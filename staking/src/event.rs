@@ -0,0 +1,28 @@
+use alloc::vec::Vec;
+use casper_types::{Key, U256};
+use cep47::TokenId;
+
+pub enum StakingEvent {
+    Stake {
+        staker: Key,
+        token_ids: Vec<TokenId>,
+    },
+    Unstake {
+        staker: Key,
+        token_ids: Vec<TokenId>,
+    },
+    Claim {
+        staker: Key,
+        amount: U256,
+    },
+    Approve {
+        owner: Key,
+        spender: Key,
+        amount: U256,
+    },
+    Transfer {
+        sender: Key,
+        recipient: Key,
+        amount: U256,
+    },
+}
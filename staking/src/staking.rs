@@ -0,0 +1,268 @@
+use crate::{
+    data::{self, Allowances, Balances, StakedTokens, Stakes},
+    event::StakingEvent,
+};
+use alloc::{string::String, vec::Vec};
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{runtime_args, ApiError, ContractHash, Key, RuntimeArgs, U256};
+use cep47::TokenId;
+use contract_utils::{ContractContext, ContractStorage};
+
+#[repr(u16)]
+pub enum Error {
+    PermissionDenied = 1,
+    WrongArguments = 2,
+    NotStaked = 3,
+    AlreadyStaked = 4,
+    InsufficientBalance = 5,
+    InsufficientAllowance = 6,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+/// Stakes tokens of a single CEP47 collection for a fungible reward. Every
+/// staked token accrues `reward_rate` reward units per second, settled into
+/// the staker's reward balance on claim and unstake. The collection has to
+/// use numeric `TokenId`s; string id collections can't be staked.
+pub trait Staking<Storage: ContractStorage>: ContractContext<Storage> {
+    fn init(
+        &mut self,
+        nft_contract: Key,
+        reward_rate: U256,
+        name: String,
+        symbol: String,
+        decimals: u8,
+    ) {
+        data::set_nft_contract(nft_contract);
+        data::set_reward_rate(reward_rate);
+        data::set_name(name);
+        data::set_symbol(symbol);
+        data::set_decimals(decimals);
+        data::set_total_supply(U256::zero());
+        Stakes::init();
+        StakedTokens::init();
+        Balances::init();
+        Allowances::init();
+    }
+
+    fn nft_contract(&self) -> Key {
+        data::nft_contract()
+    }
+
+    fn reward_rate(&self) -> U256 {
+        data::reward_rate()
+    }
+
+    fn name(&self) -> String {
+        data::name()
+    }
+
+    fn symbol(&self) -> String {
+        data::symbol()
+    }
+
+    fn decimals(&self) -> u8 {
+        data::decimals()
+    }
+
+    fn total_supply(&self) -> U256 {
+        data::total_supply()
+    }
+
+    fn balance_of(&self, owner: Key) -> U256 {
+        Balances::instance().get(&owner)
+    }
+
+    fn allowance(&self, owner: Key, spender: Key) -> U256 {
+        Allowances::instance().get(&owner, &spender)
+    }
+
+    fn staker_of(&self, token_id: TokenId) -> Option<Key> {
+        Stakes::instance()
+            .get(&token_id)
+            .map(|(staker, _, _)| staker)
+    }
+
+    fn staked_tokens(&self, staker: Key) -> Vec<TokenId> {
+        StakedTokens::instance().get(&staker)
+    }
+
+    /// Rewards accrued and not yet claimed by `token_ids`.
+    fn pending_rewards(&self, token_ids: Vec<TokenId>) -> Result<U256, Error> {
+        let stakes_dict = Stakes::instance();
+        let now = now();
+        let mut total = U256::zero();
+        for token_id in token_ids.iter() {
+            let (_, since, carry) = stakes_dict.get(token_id).ok_or(Error::NotStaked)?;
+            total += self.accrued(since, now, carry).0;
+        }
+        Ok(total)
+    }
+
+    fn stake(&mut self, token_ids: Vec<TokenId>) -> Result<(), Error> {
+        let staker = self.get_caller();
+        let stakes_dict = Stakes::instance();
+        for token_id in token_ids.iter() {
+            if stakes_dict.get(token_id).is_some() {
+                return Err(Error::AlreadyStaked);
+            }
+        }
+
+        let _: () = runtime::call_contract(
+            nft_contract_hash(),
+            "transfer_from",
+            runtime_args! {
+                "sender" => staker,
+                "recipient" => self.self_addr(),
+                "token_ids" => token_ids.clone()
+            },
+        );
+
+        let now = now();
+        let staked_tokens_dict = StakedTokens::instance();
+        let mut staked = staked_tokens_dict.get(&staker);
+        for token_id in token_ids.iter() {
+            stakes_dict.set(token_id, (staker, now, 0));
+            staked.push(*token_id);
+        }
+        staked_tokens_dict.set(&staker, staked);
+        self.emit(StakingEvent::Stake { staker, token_ids });
+        Ok(())
+    }
+
+    /// Credits the rewards accrued by `token_ids` and restarts their accrual,
+    /// carrying over the fraction of a unit not yet paid.
+    fn claim(&mut self, token_ids: Vec<TokenId>) -> Result<U256, Error> {
+        let staker = self.get_caller();
+        let stakes_dict = Stakes::instance();
+        let now = now();
+        let mut amount = U256::zero();
+        for token_id in token_ids.iter() {
+            let (since, carry) = self.assert_staker(staker, *token_id)?;
+            let (accrued, carry) = self.accrued(since, now, carry);
+            amount += accrued;
+            stakes_dict.set(token_id, (staker, now, carry));
+        }
+        self.mint_rewards(staker, amount);
+        Ok(amount)
+    }
+
+    /// Claims for `token_ids` and returns them to the staker.
+    fn unstake(&mut self, token_ids: Vec<TokenId>) -> Result<(), Error> {
+        let staker = self.get_caller();
+        self.claim(token_ids.clone())?;
+
+        let stakes_dict = Stakes::instance();
+        let staked_tokens_dict = StakedTokens::instance();
+        let mut staked = staked_tokens_dict.get(&staker);
+        for token_id in token_ids.iter() {
+            stakes_dict.remove(token_id);
+            staked.retain(|id| id != token_id);
+        }
+        staked_tokens_dict.set(&staker, staked);
+
+        let _: () = runtime::call_contract(
+            nft_contract_hash(),
+            "transfer",
+            runtime_args! {
+                "recipient" => staker,
+                "token_ids" => token_ids.clone()
+            },
+        );
+        self.emit(StakingEvent::Unstake { staker, token_ids });
+        Ok(())
+    }
+
+    fn transfer(&mut self, recipient: Key, amount: U256) -> Result<(), Error> {
+        let sender = self.get_caller();
+        self.move_rewards(sender, recipient, amount)
+    }
+
+    /// Lets `spender` move up to `amount` of the caller's rewards, replacing
+    /// any previous allowance.
+    fn approve(&mut self, spender: Key, amount: U256) {
+        let owner = self.get_caller();
+        Allowances::instance().set(&owner, &spender, amount);
+        self.emit(StakingEvent::Approve {
+            owner,
+            spender,
+            amount,
+        });
+    }
+
+    /// Moves `amount` of `owner`'s rewards on behalf of the caller, spending
+    /// the caller's allowance.
+    fn transfer_from(&mut self, owner: Key, recipient: Key, amount: U256) -> Result<(), Error> {
+        let spender = self.get_caller();
+        let allowances_dict = Allowances::instance();
+        let allowance = allowances_dict.get(&owner, &spender);
+        if allowance < amount {
+            return Err(Error::InsufficientAllowance);
+        }
+        allowances_dict.set(&owner, &spender, allowance - amount);
+        self.move_rewards(owner, recipient, amount)
+    }
+
+    fn move_rewards(&mut self, sender: Key, recipient: Key, amount: U256) -> Result<(), Error> {
+        let balances_dict = Balances::instance();
+        let sender_balance = balances_dict.get(&sender);
+        if sender_balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+        balances_dict.set(&sender, sender_balance - amount);
+        balances_dict.set(&recipient, balances_dict.get(&recipient) + amount);
+        self.emit(StakingEvent::Transfer {
+            sender,
+            recipient,
+            amount,
+        });
+        Ok(())
+    }
+
+    fn assert_staker(&self, staker: Key, token_id: TokenId) -> Result<(u64, u64), Error> {
+        let (owner, since, carry) = Stakes::instance().get(&token_id).ok_or(Error::NotStaked)?;
+        if owner != staker {
+            return Err(Error::PermissionDenied);
+        }
+        Ok((since, carry))
+    }
+
+    /// Whole reward units accrued since `since`, and the thousandths of a
+    /// unit left over.
+    fn accrued(&self, since: u64, now: u64, carry: u64) -> (U256, u64) {
+        // Block time is in milliseconds, the rate is per second.
+        let milli = self.reward_rate() * U256::from(now.saturating_sub(since)) + U256::from(carry);
+        let (amount, carry) = milli.div_mod(U256::from(1_000u64));
+        (amount, carry.as_u64())
+    }
+
+    fn mint_rewards(&mut self, staker: Key, amount: U256) {
+        if amount.is_zero() {
+            return;
+        }
+        let balances_dict = Balances::instance();
+        balances_dict.set(&staker, balances_dict.get(&staker) + amount);
+        data::set_total_supply(data::total_supply() + amount);
+        self.emit(StakingEvent::Claim { staker, amount });
+    }
+
+    fn emit(&mut self, event: StakingEvent) {
+        data::emit(&event);
+    }
+}
+
+fn now() -> u64 {
+    runtime::get_blocktime().into()
+}
+
+fn nft_contract_hash() -> ContractHash {
+    ContractHash::new(
+        data::nft_contract()
+            .into_hash()
+            .unwrap_or_revert_with(Error::WrongArguments),
+    )
+}
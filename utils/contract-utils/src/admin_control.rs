@@ -34,6 +34,12 @@ pub trait AdminControl<Storage: ContractStorage>: ContractContext<Storage> {
     fn is_admin(&self, address: Key) -> bool {
         Admins::instance().is_admin(&address)
     }
+
+    /// Moves the admin entry of `address` off its legacy item key, see
+    /// `LEGACY_KEY_STRS`.
+    fn migrate_admin_key(&mut self, address: Key) {
+        Admins::instance().migrate(&address);
+    }
 }
 
 struct Admins {
@@ -61,4 +67,8 @@ impl Admins {
     pub fn disable_admin(&self, key: &Key) {
         self.dict.remove_by_key::<()>(key);
     }
+
+    pub fn migrate(&self, key: &Key) {
+        self.dict.migrate_by_key::<()>(key);
+    }
 }
@@ -7,9 +7,14 @@ use casper_contract::{
 };
 use casper_types::{
     bytesrepr::{FromBytes, ToBytes},
-    ApiError, CLTyped, Key, URef,
+    CLTyped, Key, URef,
 };
 
+/// Named key that makes `Key` lookups fall back to the item keys accounts
+/// and hashes had before `key_to_str` hashed every variant. Upgraded
+/// contracts set it while such items may remain.
+pub const LEGACY_KEY_STRS: &str = "legacy_key_strs";
+
 pub struct Dict {
     uref: URef,
 }
@@ -37,6 +42,7 @@ impl Dict {
 
     pub fn get_by_key<T: CLTyped + FromBytes>(&self, key: &Key) -> Option<T> {
         self.get(&key_to_str(key))
+            .or_else(|| legacy_key_to_str(key).and_then(|legacy| self.get(&legacy)))
     }
 
     pub fn get_by_keys<T: CLTyped + FromBytes>(&self, keys: (&Key, &Key)) -> Option<T> {
@@ -49,6 +55,7 @@ impl Dict {
 
     pub fn set_by_key<T: CLTyped + ToBytes>(&self, key: &Key, value: T) {
         self.set(&key_to_str(key), value);
+        self.remove_legacy::<T>(key);
     }
 
     pub fn set_by_keys<T: CLTyped + ToBytes>(&self, keys: (&Key, &Key), value: T) {
@@ -61,6 +68,23 @@ impl Dict {
 
     pub fn remove_by_key<T: CLTyped + ToBytes>(&self, key: &Key) {
         self.remove::<T>(&key_to_str(key));
+        self.remove_legacy::<T>(key);
+    }
+
+    /// Moves the item of `key` from its legacy to its current item key.
+    pub fn migrate_by_key<T: CLTyped + FromBytes + ToBytes>(&self, key: &Key) {
+        if let Some(legacy) = legacy_key_to_str(key) {
+            if let Some(value) = self.get::<T>(&legacy) {
+                self.set(&key_to_str(key), value);
+                self.remove::<T>(&legacy);
+            }
+        }
+    }
+
+    fn remove_legacy<T: CLTyped + ToBytes>(&self, key: &Key) {
+        if let Some(legacy) = legacy_key_to_str(key) {
+            self.remove::<T>(&legacy);
+        }
     }
 
     pub fn remove_by_vec_of_keys<T: CLTyped + ToBytes>(&self, keys: (&Key, &Key)) {
@@ -68,14 +92,39 @@ impl Dict {
     }
 }
 
+/// Dictionary item key for `key`: the hex encoded blake2b digest of its
+/// bytesrepr, which starts with the variant tag, so no two variants share an
+/// item and every item key is 64 characters. URefs drop their access rights
+/// so a purse maps to the same item however it is held.
 pub fn key_to_str(key: &Key) -> String {
+    let key = match key {
+        Key::URef(uref) => Key::URef(uref.remove_access_rights()),
+        _ => *key,
+    };
+    hex::encode(runtime::blake2b(key.to_bytes().unwrap_or_revert()))
+}
+
+/// Plain hex item key accounts and hashes were stored under before
+/// `key_to_str` hashed them, while `LEGACY_KEY_STRS` is set.
+pub fn legacy_key_to_str(key: &Key) -> Option<String> {
+    if !legacy_key_strs() {
+        return None;
+    }
     match key {
-        Key::Account(account) => account.to_string(),
-        Key::Hash(package) => hex::encode(package),
-        _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+        Key::Account(account) => Some(account.to_string()),
+        Key::Hash(hash) => Some(hex::encode(hash)),
+        _ => None,
     }
 }
 
+pub fn legacy_key_strs() -> bool {
+    get_key(LEGACY_KEY_STRS).unwrap_or_default()
+}
+
+pub fn set_legacy_key_strs(legacy: bool) {
+    set_key(LEGACY_KEY_STRS, legacy);
+}
+
 pub fn keys_to_str(key_a: &Key, key_b: &Key) -> String {
     let mut bytes_a = key_a.to_bytes().unwrap_or_revert();
     let mut bytes_b = key_b.to_bytes().unwrap_or_revert();
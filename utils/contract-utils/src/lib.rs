@@ -7,8 +7,15 @@ mod admin_control;
 mod contract_context;
 mod contract_storage;
 mod data;
+mod named_args;
+mod typed_dict;
 
 pub use admin_control::AdminControl;
 pub use contract_context::ContractContext;
 pub use contract_storage::{ContractStorage, OnChainContractStorage};
-pub use data::{get_key, key_and_value_to_str, key_to_str, set_key, Dict};
+pub use data::{
+    get_key, key_and_value_to_str, key_to_str, legacy_key_strs, legacy_key_to_str, set_key,
+    set_legacy_key_strs, Dict, LEGACY_KEY_STRS,
+};
+pub use named_args::get_optional_named_arg;
+pub use typed_dict::{Blake2bKey, HexKey, KeyEncoder, KeyStr, StringKey, TypedDict};
@@ -0,0 +1,139 @@
+use alloc::string::{String, ToString};
+use core::marker::PhantomData;
+
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    ApiError, CLTyped, Key, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH,
+};
+
+use crate::{key_to_str, legacy_key_to_str, Dict};
+
+/// Turns a dictionary key into the item key it is stored under.
+pub trait KeyEncoder<K: ?Sized> {
+    fn encode(key: &K) -> String;
+
+    /// Item key `key` was stored under by an earlier encoding, looked up when
+    /// nothing is stored under `encode`.
+    fn legacy_encode(_key: &K) -> Option<String> {
+        None
+    }
+}
+
+/// Stores items under the key's `to_string`, e.g. decimal token ids.
+pub struct StringKey;
+
+impl<K: ToString + ?Sized> KeyEncoder<K> for StringKey {
+    fn encode(key: &K) -> String {
+        key.to_string()
+    }
+}
+
+/// Stores items under the hex encoded bytesrepr of the key.
+pub struct HexKey;
+
+impl<K: ToBytes + ?Sized> KeyEncoder<K> for HexKey {
+    fn encode(key: &K) -> String {
+        hex::encode(key.to_bytes().unwrap_or_revert())
+    }
+}
+
+/// Stores items under the hex encoded blake2b digest of the key's
+/// bytesrepr, 64 characters whatever the key.
+pub struct Blake2bKey;
+
+impl<K: ToBytes + ?Sized> KeyEncoder<K> for Blake2bKey {
+    fn encode(key: &K) -> String {
+        hex::encode(runtime::blake2b(key.to_bytes().unwrap_or_revert()))
+    }
+}
+
+/// Stores items under `key_to_str` of a `Key`.
+pub struct KeyStr;
+
+impl KeyEncoder<Key> for KeyStr {
+    fn encode(key: &Key) -> String {
+        key_to_str(key)
+    }
+
+    fn legacy_encode(key: &Key) -> Option<String> {
+        legacy_key_to_str(key)
+    }
+}
+
+/// A `Dict` holding values of type `V` under keys of type `K`, stored with
+/// the item keys `E` encodes.
+pub struct TypedDict<K: ?Sized, V, E> {
+    dict: Dict,
+    _marker: PhantomData<(fn(&K), V, E)>,
+}
+
+impl<K, V, E> TypedDict<K, V, E>
+where
+    K: ?Sized,
+    V: CLTyped + ToBytes + FromBytes,
+    E: KeyEncoder<K>,
+{
+    pub fn instance(name: &str) -> Self {
+        Self::wrap(Dict::instance(name))
+    }
+
+    pub fn init(name: &str) {
+        Dict::init(name)
+    }
+
+    pub fn at(uref: URef) -> Self {
+        Self::wrap(Dict::at(uref))
+    }
+
+    fn wrap(dict: Dict) -> Self {
+        TypedDict {
+            dict,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.dict
+            .get(&Self::item_key(key))
+            .or_else(|| E::legacy_encode(key).and_then(|legacy| self.dict.get(&legacy)))
+    }
+
+    pub fn set(&self, key: &K, value: V) {
+        self.dict.set(&Self::item_key(key), value);
+        self.remove_legacy(key);
+    }
+
+    pub fn remove(&self, key: &K) {
+        self.dict.remove::<V>(&Self::item_key(key));
+        self.remove_legacy(key);
+    }
+
+    /// Moves the item of `key` from its legacy to its current item key.
+    pub fn migrate(&self, key: &K) {
+        if let Some(legacy) = E::legacy_encode(key) {
+            if let Some(value) = self.dict.get::<V>(&legacy) {
+                self.dict.set(&Self::item_key(key), value);
+                self.dict.remove::<V>(&legacy);
+            }
+        }
+    }
+
+    fn remove_legacy(&self, key: &K) {
+        if let Some(legacy) = E::legacy_encode(key) {
+            if self.dict.get::<V>(&legacy).is_some() {
+                self.dict.remove::<V>(&legacy);
+            }
+        }
+    }
+
+    /// Encodes `key`, reverting with a dedicated error instead of letting
+    /// the host reject an item key that is too long.
+    pub fn item_key(key: &K) -> String {
+        let item_key = E::encode(key);
+        if item_key.len() > DICTIONARY_ITEM_KEY_MAX_LENGTH {
+            runtime::revert(ApiError::DictionaryItemKeyExceedsLength);
+        }
+        item_key
+    }
+}
@@ -31,6 +31,13 @@ impl TestContract {
         }
     }
 
+    pub fn upgrade(&self, wasm: &str, mut args: RuntimeArgs) {
+        let session_code = PathBuf::from(wasm);
+        args.insert("contract_name", self.name.clone()).unwrap();
+        self.env
+            .run(self.contract_owner, DeploySource::Code(session_code), args);
+    }
+
     pub fn query_dictionary<T: CLTyped + FromBytes>(
         &self,
         dict_name: &str,
@@ -52,6 +59,14 @@ impl TestContract {
             .query_account_named_key(self.contract_owner, &[key])
     }
 
+    pub fn package_hash(&self) -> [u8; 32] {
+        let key = format!("{}_contract_package_hash", self.name);
+        self.env
+            .account_named_key(self.contract_owner, &key)
+            .into_hash()
+            .expect("should be hash.")
+    }
+
     pub fn call_contract(&self, sender: AccountHash, entry_point: &str, session_args: RuntimeArgs) {
         let session_code = DeploySource::ByHash {
             hash: ContractHash::new(self.contract_hash()),
@@ -1,8 +1,12 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 
 use casper_engine_test_support::{InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST};
 use casper_types::{
     account::AccountHash, bytesrepr::FromBytes, CLTyped, Key, PublicKey, RuntimeArgs, SecretKey,
+    U512,
 };
 
 use crate::utils::{deploy, fund_account, query, query_dictionary_item, DeploySource};
@@ -20,13 +24,28 @@ impl TestEnv {
     }
 
     pub fn run(&self, sender: AccountHash, session_code: DeploySource, session_args: RuntimeArgs) {
+        let mut state = self.state.lock().unwrap();
+        let block_time = state.block_time;
         deploy(
-            &mut self.state.lock().unwrap().builder,
+            &mut state.builder,
             &sender,
             &session_code,
             session_args,
             true,
-            None,
+            block_time,
+        )
+    }
+
+    /// Sets the block time, in milliseconds, seen by all following deploys.
+    pub fn set_block_time(&self, block_time: u64) {
+        self.state.lock().unwrap().block_time = Some(block_time);
+    }
+
+    pub fn run_session(&self, sender: AccountHash, wasm: &str, session_args: RuntimeArgs) {
+        self.run(
+            sender,
+            DeploySource::Code(PathBuf::from(wasm)),
+            session_args,
         )
     }
 
@@ -34,6 +53,14 @@ impl TestEnv {
         self.state.lock().unwrap().next_user()
     }
 
+    pub fn account_balance(&self, account: AccountHash) -> U512 {
+        self.state.lock().unwrap().account_balance(account)
+    }
+
+    pub fn account_named_key(&self, account: AccountHash, name: &str) -> Key {
+        self.state.lock().unwrap().account_named_key(account, name)
+    }
+
     pub fn query_dictionary<T: CLTyped + FromBytes>(
         &self,
         contract_hash: [u8; 32],
@@ -67,6 +94,7 @@ impl Default for TestEnv {
 struct TestEnvState {
     builder: InMemoryWasmTestBuilder,
     accounts: Vec<AccountHash>,
+    block_time: Option<u64>,
 }
 
 impl TestEnvState {
@@ -85,7 +113,11 @@ impl TestEnvState {
                 .commit();
         }
 
-        TestEnvState { builder, accounts }
+        TestEnvState {
+            builder,
+            accounts,
+            block_time: None,
+        }
     }
 
     pub fn _new_with_users(user_secrets: &[[u8; 32]]) -> TestEnvState {
@@ -104,7 +136,11 @@ impl TestEnvState {
                 .commit();
         }
 
-        TestEnvState { builder, accounts }
+        TestEnvState {
+            builder,
+            accounts,
+            block_time: None,
+        }
     }
 
     pub fn next_user(&mut self) -> AccountHash {
@@ -159,4 +195,23 @@ impl TestEnvState {
     ) -> T {
         query(&self.builder, Key::Account(account), path)
     }
+
+    pub fn account_balance(&self, account: AccountHash) -> U512 {
+        let purse = self
+            .builder
+            .get_account(account)
+            .expect("should be account.")
+            .main_purse();
+        self.builder.get_purse_balance(purse)
+    }
+
+    pub fn account_named_key(&self, account: AccountHash, name: &str) -> Key {
+        *self
+            .builder
+            .get_account(account)
+            .expect("should be account.")
+            .named_keys()
+            .get(name)
+            .expect("should have named key.")
+    }
 }
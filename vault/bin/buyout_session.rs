@@ -0,0 +1,27 @@
+#![no_main]
+#![no_std]
+
+use casper_contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractHash, Key, RuntimeArgs, U512};
+
+// Funds a fresh purse with `amount` and buys the vaulted token with it.
+#[no_mangle]
+fn call() {
+    let vault_contract_hash: Key = runtime::get_named_arg("vault_contract_hash");
+    let amount: U512 = runtime::get_named_arg("amount");
+
+    let purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), purse, amount, None)
+        .unwrap_or_revert();
+
+    let _: () = runtime::call_contract(
+        ContractHash::new(vault_contract_hash.into_hash().unwrap_or_revert()),
+        "buyout",
+        runtime_args! {
+            "purse" => purse
+        },
+    );
+}
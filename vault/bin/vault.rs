@@ -0,0 +1,320 @@
+#![no_main]
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::{collections::BTreeSet, format, string::String};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, CLTyped, CLValue, ContractPackageHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U256, U512,
+};
+use cep47::TokenId;
+use contract_utils::{ContractContext, OnChainContractStorage};
+use vault::Vault;
+
+#[derive(Default)]
+struct VaultContract(OnChainContractStorage);
+
+impl ContractContext<OnChainContractStorage> for VaultContract {
+    fn storage(&self) -> &OnChainContractStorage {
+        &self.0
+    }
+}
+
+impl Vault<OnChainContractStorage> for VaultContract {}
+
+#[no_mangle]
+fn constructor() {
+    let name = runtime::get_named_arg::<String>("name");
+    let symbol = runtime::get_named_arg::<String>("symbol");
+    let curator = runtime::get_named_arg::<Key>("curator");
+    let nft_contract = runtime::get_named_arg::<Key>("nft_contract");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let share_supply = runtime::get_named_arg::<U256>("share_supply");
+    let reserve_price = runtime::get_named_arg::<U512>("reserve_price");
+    VaultContract::default().init(
+        name,
+        symbol,
+        curator,
+        nft_contract,
+        token_id,
+        share_supply,
+        reserve_price,
+    );
+}
+
+#[no_mangle]
+fn name() {
+    let ret = VaultContract::default().name();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn symbol() {
+    let ret = VaultContract::default().symbol();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn total_supply() {
+    let ret = VaultContract::default().total_supply();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn reserve_price() {
+    let ret = VaultContract::default().reserve_price();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn state() {
+    let ret = VaultContract::default().state();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn balance_of() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let ret = VaultContract::default().balance_of(owner);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn allowance() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let spender = runtime::get_named_arg::<Key>("spender");
+    let ret = VaultContract::default().allowance(owner, spender);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn lock() {
+    VaultContract::default().lock().unwrap_or_revert();
+}
+
+#[no_mangle]
+fn buyout() {
+    let purse = runtime::get_named_arg::<URef>("purse");
+    VaultContract::default().buyout(purse).unwrap_or_revert();
+}
+
+#[no_mangle]
+fn redeem() {
+    let ret = VaultContract::default().redeem().unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn transfer() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let amount = runtime::get_named_arg::<U256>("amount");
+    VaultContract::default()
+        .transfer(recipient, amount)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn approve() {
+    let spender = runtime::get_named_arg::<Key>("spender");
+    let amount = runtime::get_named_arg::<U256>("amount");
+    VaultContract::default().approve(spender, amount);
+}
+
+#[no_mangle]
+fn transfer_from() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let amount = runtime::get_named_arg::<U256>("amount");
+    VaultContract::default()
+        .transfer_from(owner, recipient, amount)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn call() {
+    // Read arguments for the constructor call.
+    let name: String = runtime::get_named_arg("name");
+    let symbol: String = runtime::get_named_arg("symbol");
+    let nft_contract: Key = runtime::get_named_arg("nft_contract");
+    let token_id: TokenId = runtime::get_named_arg("token_id");
+    let share_supply: U256 = runtime::get_named_arg("share_supply");
+    let reserve_price: U512 = runtime::get_named_arg("reserve_price");
+    let contract_name: String = runtime::get_named_arg("contract_name");
+
+    // The installing account becomes the curator.
+    let constructor_args = runtime_args! {
+        "name" => name,
+        "symbol" => symbol,
+        "curator" => Key::from(runtime::get_caller()),
+        "nft_contract" => nft_contract,
+        "token_id" => token_id,
+        "share_supply" => share_supply,
+        "reserve_price" => reserve_price
+    };
+
+    let (contract_hash, _) = storage::new_contract(
+        get_entry_points(),
+        None,
+        Some(format!("{}_contract_package_hash", contract_name)),
+        None,
+    );
+
+    let package_hash: ContractPackageHash = ContractPackageHash::new(
+        runtime::get_key(&format!("{}_contract_package_hash", contract_name))
+            .unwrap_or_revert()
+            .into_hash()
+            .unwrap_or_revert(),
+    );
+
+    let constructor_access: URef =
+        storage::create_contract_user_group(package_hash, "constructor", 1, Default::default())
+            .unwrap_or_revert()
+            .pop()
+            .unwrap_or_revert();
+
+    let _: () = runtime::call_contract(contract_hash, "constructor", constructor_args);
+
+    let mut urefs = BTreeSet::new();
+    urefs.insert(constructor_access);
+    storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
+        .unwrap_or_revert();
+
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash_wrapped", contract_name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "constructor",
+        vec![
+            Parameter::new("name", String::cl_type()),
+            Parameter::new("symbol", String::cl_type()),
+            Parameter::new("curator", Key::cl_type()),
+            Parameter::new("nft_contract", Key::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+            Parameter::new("share_supply", U256::cl_type()),
+            Parameter::new("reserve_price", U512::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("constructor")]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "name",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "symbol",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_supply",
+        vec![],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "reserve_price",
+        vec![],
+        U512::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "state",
+        vec![],
+        u8::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of",
+        vec![Parameter::new("owner", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "allowance",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("spender", Key::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "lock",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "buyout",
+        vec![Parameter::new("purse", URef::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "redeem",
+        vec![],
+        U512::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "approve",
+        vec![
+            Parameter::new("spender", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_from",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
@@ -0,0 +1,253 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_contract::{
+    contract_api::{
+        runtime::{self, get_call_stack},
+        storage,
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{system::CallStackElement, ContractPackageHash, Key, URef, U256, U512};
+use cep47::TokenId;
+use contract_utils::{get_key, set_key, Dict};
+
+use crate::event::VaultEvent;
+
+const BALANCES_DICT: &str = "balances";
+const ALLOWANCES_DICT: &str = "allowances";
+const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+
+pub const NAME: &str = "name";
+pub const SYMBOL: &str = "symbol";
+pub const TOTAL_SUPPLY: &str = "total_supply";
+pub const SHARE_SUPPLY: &str = "share_supply";
+pub const CURATOR: &str = "curator";
+pub const NFT_CONTRACT: &str = "nft_contract";
+pub const TOKEN_ID: &str = "token_id";
+pub const RESERVE_PRICE: &str = "reserve_price";
+pub const STATE: &str = "state";
+pub const PURSE: &str = "purse";
+
+/// Share balances.
+pub struct Balances {
+    dict: Dict,
+}
+
+impl Balances {
+    pub fn instance() -> Balances {
+        Balances {
+            dict: Dict::instance(BALANCES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(BALANCES_DICT)
+    }
+
+    pub fn get(&self, owner: &Key) -> U256 {
+        self.dict.get_by_key(owner).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, value: U256) {
+        self.dict.set_by_key(owner, value);
+    }
+}
+
+/// Share allowances keyed by owner and spender.
+pub struct Allowances {
+    dict: Dict,
+}
+
+impl Allowances {
+    pub fn instance() -> Allowances {
+        Allowances {
+            dict: Dict::instance(ALLOWANCES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(ALLOWANCES_DICT)
+    }
+
+    pub fn get(&self, owner: &Key, spender: &Key) -> U256 {
+        self.dict.get_by_keys((owner, spender)).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, spender: &Key, value: U256) {
+        self.dict.set_by_keys((owner, spender), value);
+    }
+}
+
+pub fn name() -> String {
+    get_key(NAME).unwrap_or_revert()
+}
+
+pub fn set_name(name: String) {
+    set_key(NAME, name);
+}
+
+pub fn symbol() -> String {
+    get_key(SYMBOL).unwrap_or_revert()
+}
+
+pub fn set_symbol(symbol: String) {
+    set_key(SYMBOL, symbol);
+}
+
+/// Shares currently in circulation, reduced as shares are redeemed.
+pub fn total_supply() -> U256 {
+    get_key(TOTAL_SUPPLY).unwrap_or_default()
+}
+
+pub fn set_total_supply(total_supply: U256) {
+    set_key(TOTAL_SUPPLY, total_supply);
+}
+
+/// Shares minted to the curator when the token is locked.
+pub fn share_supply() -> U256 {
+    get_key(SHARE_SUPPLY).unwrap_or_revert()
+}
+
+pub fn set_share_supply(share_supply: U256) {
+    set_key(SHARE_SUPPLY, share_supply);
+}
+
+pub fn curator() -> Key {
+    get_key(CURATOR).unwrap_or_revert()
+}
+
+pub fn set_curator(curator: Key) {
+    set_key(CURATOR, curator);
+}
+
+pub fn nft_contract() -> Key {
+    get_key(NFT_CONTRACT).unwrap_or_revert()
+}
+
+pub fn set_nft_contract(nft_contract: Key) {
+    set_key(NFT_CONTRACT, nft_contract);
+}
+
+pub fn token_id() -> TokenId {
+    get_key(TOKEN_ID).unwrap_or_revert()
+}
+
+pub fn set_token_id(token_id: TokenId) {
+    set_key(TOKEN_ID, token_id);
+}
+
+pub fn reserve_price() -> U512 {
+    get_key(RESERVE_PRICE).unwrap_or_revert()
+}
+
+pub fn set_reserve_price(reserve_price: U512) {
+    set_key(RESERVE_PRICE, reserve_price);
+}
+
+pub fn state() -> u8 {
+    get_key(STATE).unwrap_or_revert()
+}
+
+pub fn set_state(state: u8) {
+    set_key(STATE, state);
+}
+
+/// Purse holding the buyout proceeds until they are redeemed.
+pub fn purse() -> URef {
+    *runtime::get_key(PURSE)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert()
+}
+
+pub fn set_purse(purse: URef) {
+    runtime::put_key(PURSE, purse.into());
+}
+
+pub fn contract_package_hash() -> ContractPackageHash {
+    let call_stacks = get_call_stack();
+    let last_entry = call_stacks.last().unwrap_or_revert();
+    let package_hash: Option<ContractPackageHash> = match last_entry {
+        CallStackElement::StoredContract {
+            contract_package_hash,
+            contract_hash: _,
+        } => Some(*contract_package_hash),
+        _ => None,
+    };
+    package_hash.unwrap_or_revert()
+}
+
+pub fn emit(event: &VaultEvent) {
+    let mut events = Vec::new();
+    let package = contract_package_hash();
+    match event {
+        VaultEvent::Lock {
+            curator,
+            nft_contract,
+            token_id,
+            shares,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "vault_lock".to_string());
+            param.insert("curator", curator.to_string());
+            param.insert("nft_contract", nft_contract.to_string());
+            param.insert("token_id", token_id.to_string());
+            param.insert("shares", shares.to_string());
+            events.push(param);
+        }
+        VaultEvent::Buyout { buyer, price } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "vault_buyout".to_string());
+            param.insert("buyer", buyer.to_string());
+            param.insert("price", price.to_string());
+            events.push(param);
+        }
+        VaultEvent::Redeem {
+            owner,
+            shares,
+            amount,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "vault_redeem".to_string());
+            param.insert("owner", owner.to_string());
+            param.insert("shares", shares.to_string());
+            param.insert("amount", amount.to_string());
+            events.push(param);
+        }
+        VaultEvent::Transfer {
+            sender,
+            recipient,
+            amount,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "vault_transfer".to_string());
+            param.insert("sender", sender.to_string());
+            param.insert("recipient", recipient.to_string());
+            param.insert("amount", amount.to_string());
+            events.push(param);
+        }
+        VaultEvent::Approve {
+            owner,
+            spender,
+            amount,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "vault_approve".to_string());
+            param.insert("owner", owner.to_string());
+            param.insert("spender", spender.to_string());
+            param.insert("amount", amount.to_string());
+            events.push(param);
+        }
+    };
+    for param in events {
+        let _: URef = storage::new_uref(param);
+    }
+}
@@ -0,0 +1,30 @@
+use casper_types::{Key, U256, U512};
+use cep47::TokenId;
+
+pub enum VaultEvent {
+    Lock {
+        curator: Key,
+        nft_contract: Key,
+        token_id: TokenId,
+        shares: U256,
+    },
+    Buyout {
+        buyer: Key,
+        price: U512,
+    },
+    Redeem {
+        owner: Key,
+        shares: U256,
+        amount: U512,
+    },
+    Transfer {
+        sender: Key,
+        recipient: Key,
+        amount: U256,
+    },
+    Approve {
+        owner: Key,
+        spender: Key,
+        amount: U256,
+    },
+}
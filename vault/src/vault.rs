@@ -0,0 +1,264 @@
+use crate::{
+    data::{self, Allowances, Balances},
+    event::VaultEvent,
+};
+use alloc::{string::String, vec};
+use casper_contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ApiError, ContractHash, Key, RuntimeArgs, URef, U256, U512};
+use cep47::TokenId;
+use contract_utils::{ContractContext, ContractStorage};
+
+#[repr(u16)]
+pub enum Error {
+    PermissionDenied = 1,
+    WrongArguments = 2,
+    WrongState = 3,
+    WrongPaymentAmount = 4,
+    InsufficientBalance = 5,
+    InsufficientAllowance = 6,
+    InvalidRecipient = 7,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum VaultState {
+    /// Waiting for the curator to lock the token.
+    Pending = 0,
+    /// The token is held by the vault and open to a buyout.
+    Locked = 1,
+    /// The token was bought out, shares redeem the proceeds.
+    BoughtOut = 2,
+}
+
+/// Fractionalizes a single CEP47 token. The curator locks the token with
+/// `transfer_from`, so the vault has to be its approved spender, and
+/// receives the whole share supply. Anyone may buy the token for the
+/// reserve price, after which each share redeems an equal part of it.
+/// Redeeming pays out to the holder's main purse, so shares can only be held
+/// by accounts. The token must come from a collection with numeric ids.
+pub trait Vault<Storage: ContractStorage>: ContractContext<Storage> {
+    #[allow(clippy::too_many_arguments)]
+    fn init(
+        &mut self,
+        name: String,
+        symbol: String,
+        curator: Key,
+        nft_contract: Key,
+        token_id: TokenId,
+        share_supply: U256,
+        reserve_price: U512,
+    ) {
+        data::set_name(name);
+        data::set_symbol(symbol);
+        data::set_curator(curator);
+        data::set_nft_contract(nft_contract);
+        data::set_token_id(token_id);
+        data::set_share_supply(share_supply);
+        data::set_reserve_price(reserve_price);
+        data::set_total_supply(U256::zero());
+        data::set_state(VaultState::Pending as u8);
+        data::set_purse(system::create_purse());
+        Balances::init();
+        Allowances::init();
+    }
+
+    fn name(&self) -> String {
+        data::name()
+    }
+
+    fn symbol(&self) -> String {
+        data::symbol()
+    }
+
+    fn total_supply(&self) -> U256 {
+        data::total_supply()
+    }
+
+    fn reserve_price(&self) -> U512 {
+        data::reserve_price()
+    }
+
+    fn state(&self) -> u8 {
+        data::state()
+    }
+
+    fn balance_of(&self, owner: Key) -> U256 {
+        Balances::instance().get(&owner)
+    }
+
+    fn allowance(&self, owner: Key, spender: Key) -> U256 {
+        Allowances::instance().get(&owner, &spender)
+    }
+
+    /// Takes custody of the token and mints the share supply to the curator.
+    fn lock(&mut self) -> Result<(), Error> {
+        let curator = self.get_caller();
+        if curator != data::curator() {
+            return Err(Error::PermissionDenied);
+        }
+        self.assert_state(VaultState::Pending)?;
+        if curator.into_account().is_none() {
+            return Err(Error::InvalidRecipient);
+        }
+
+        let nft_contract = data::nft_contract();
+        let token_id = data::token_id();
+        let _: () = runtime::call_contract(
+            to_contract_hash(nft_contract)?,
+            "transfer_from",
+            runtime_args! {
+                "sender" => curator,
+                "recipient" => self.self_addr(),
+                "token_ids" => vec![token_id]
+            },
+        );
+
+        let shares = data::share_supply();
+        Balances::instance().set(&curator, shares);
+        data::set_total_supply(shares);
+        data::set_state(VaultState::Locked as u8);
+        self.emit(VaultEvent::Lock {
+            curator,
+            nft_contract,
+            token_id,
+            shares,
+        });
+        Ok(())
+    }
+
+    /// Buys the locked token for exactly the reserve price paid from `purse`.
+    fn buyout(&mut self, purse: URef) -> Result<(), Error> {
+        self.assert_state(VaultState::Locked)?;
+        let price = data::reserve_price();
+        if system::get_purse_balance(purse).unwrap_or_revert() != price {
+            return Err(Error::WrongPaymentAmount);
+        }
+        system::transfer_from_purse_to_purse(purse, data::purse(), price, None).unwrap_or_revert();
+        data::set_state(VaultState::BoughtOut as u8);
+
+        let buyer = self.get_caller();
+        let _: () = runtime::call_contract(
+            to_contract_hash(data::nft_contract())?,
+            "transfer",
+            runtime_args! {
+                "recipient" => buyer,
+                "token_ids" => vec![data::token_id()]
+            },
+        );
+        self.emit(VaultEvent::Buyout { buyer, price });
+        Ok(())
+    }
+
+    /// Burns the caller's shares for their pro rata part of the proceeds.
+    /// Shares are priced against what is left in the purse, so rounding dust
+    /// ends up with the last holder to redeem.
+    fn redeem(&mut self) -> Result<U512, Error> {
+        self.assert_state(VaultState::BoughtOut)?;
+        let owner = self.get_caller();
+        let account = owner.into_account().ok_or(Error::PermissionDenied)?;
+        let balances_dict = Balances::instance();
+        let shares = balances_dict.get(&owner);
+        if shares.is_zero() {
+            return Err(Error::InsufficientBalance);
+        }
+
+        let total_supply = data::total_supply();
+        let proceeds = system::get_purse_balance(data::purse()).unwrap_or_revert();
+        let amount = proceeds * to_u512(shares) / to_u512(total_supply);
+        balances_dict.set(&owner, U256::zero());
+        data::set_total_supply(total_supply - shares);
+        system::transfer_from_purse_to_account(data::purse(), account, amount, None)
+            .unwrap_or_revert();
+
+        self.emit(VaultEvent::Redeem {
+            owner,
+            shares,
+            amount,
+        });
+        Ok(amount)
+    }
+
+    fn transfer(&mut self, recipient: Key, amount: U256) -> Result<(), Error> {
+        let sender = self.get_caller();
+        self.transfer_internal(sender, recipient, amount)
+    }
+
+    fn approve(&mut self, spender: Key, amount: U256) {
+        let owner = self.get_caller();
+        Allowances::instance().set(&owner, &spender, amount);
+        self.emit(VaultEvent::Approve {
+            owner,
+            spender,
+            amount,
+        });
+    }
+
+    fn transfer_from(&mut self, owner: Key, recipient: Key, amount: U256) -> Result<(), Error> {
+        let spender = self.get_caller();
+        let allowances_dict = Allowances::instance();
+        let allowance = allowances_dict.get(&owner, &spender);
+        if allowance < amount {
+            return Err(Error::InsufficientAllowance);
+        }
+        self.transfer_internal(owner, recipient, amount)?;
+        allowances_dict.set(&owner, &spender, allowance - amount);
+        Ok(())
+    }
+
+    fn transfer_internal(
+        &mut self,
+        sender: Key,
+        recipient: Key,
+        amount: U256,
+    ) -> Result<(), Error> {
+        let balances_dict = Balances::instance();
+        if recipient.into_account().is_none() {
+            return Err(Error::InvalidRecipient);
+        }
+        let sender_balance = balances_dict.get(&sender);
+        if sender_balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+        balances_dict.set(&sender, sender_balance - amount);
+        balances_dict.set(&recipient, balances_dict.get(&recipient) + amount);
+        self.emit(VaultEvent::Transfer {
+            sender,
+            recipient,
+            amount,
+        });
+        Ok(())
+    }
+
+    fn assert_state(&self, state: VaultState) -> Result<(), Error> {
+        if data::state() != state as u8 {
+            return Err(Error::WrongState);
+        }
+        Ok(())
+    }
+
+    fn emit(&mut self, event: VaultEvent) {
+        data::emit(&event);
+    }
+}
+
+fn to_contract_hash(nft_contract: Key) -> Result<ContractHash, Error> {
+    nft_contract
+        .into_hash()
+        .map(ContractHash::new)
+        .ok_or(Error::WrongArguments)
+}
+
+fn to_u512(value: U256) -> U512 {
+    let mut bytes = [0u8; 32];
+    value.to_little_endian(&mut bytes);
+    U512::from_little_endian(&bytes)
+}